@@ -1,333 +1,5937 @@
+use serde::ser::SerializeStruct;
 use serde::{Deserialize, Serialize};
-use std::net::IpAddr;
-use std::time::Duration;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::sync::{Arc, Mutex, RwLock};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tauri::{
-    AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder,
+    AppHandle, Emitter, Listener, Manager, State, WebviewUrl, WebviewWindowBuilder,
     menu::{Menu, MenuItem},
-    tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
+    tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent},
     image::Image,
+    async_runtime::JoinHandle,
 };
-use futures::future::join_all;
+use futures::future::{self, join_all};
+use futures::stream::{self, StreamExt};
+use ipnet::Ipv4Net;
+use mdns_sd::{HostnameResolutionEvent, ServiceDaemon, ServiceEvent};
+use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_shell::ShellExt;
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+use tauri_plugin_store::StoreExt;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions};
 
-#[derive(Debug, Serialize, Deserialize)]
+// Smallest prefix length accepted by scan_network_cidr (a /16 is 65534 hosts)
+const MIN_CIDR_PREFIX: u8 = 16;
+
+// Default number of in-flight probes during a network scan
+const DEFAULT_SCAN_CONCURRENCY: usize = 64;
+
+// In-flight miner requests shared by scans, discovery and the background loops, see RequestBudget
+const GLOBAL_REQUEST_BUDGET: usize = 64;
+
+// Most passes scan_network_thorough will run over one range
+const MAX_SCAN_PASSES: u32 = 5;
+
+// Per-probe timeout used when scanning (ms)
+const DEFAULT_SCAN_TIMEOUT_MS: u64 = 1500;
+const MIN_SCAN_TIMEOUT_MS: u64 = 200;
+const MAX_SCAN_TIMEOUT_MS: u64 = 10_000;
+
+// Retry policy for get_miner_data (200ms, 400ms, 800ms)
+const DEFAULT_FETCH_RETRIES: u32 = 3;
+const RETRY_BASE_DELAY_MS: u64 = 200;
+
+// Chunk size used when streaming OTA uploads (drives ota-progress granularity)
+const OTA_CHUNK_SIZE: usize = 16 * 1024;
+
+// Thresholds at which the tray icon reports a miner as degraded
+const DEGRADED_TEMP_C: f64 = 70.0;
+const DEGRADED_REJECT_RATIO: f64 = 0.01;
+
+// Store file holding saved window geometry
+const WINDOW_STORE_FILE: &str = "windows.json";
+
+// User-Agent sent on every HTTP request unless the user_agent setting overrides it
+const DEFAULT_USER_AGENT: &str = concat!("AxeOS-Multitool/", env!("CARGO_PKG_VERSION"));
+
+// User-Agent override from the app settings; global because the request helpers
+// build their clients without an AppHandle
+static USER_AGENT_OVERRIDE: RwLock<Option<String>> = RwLock::new(None);
+
+// verbose_logging setting, read by CommandTimer (debug builds always log command timings)
+static VERBOSE_LOGGING: AtomicBool = AtomicBool::new(false);
+
+// Longest argument summary CommandTimer writes to the log
+const MAX_COMMAND_LOG_ARGS_CHARS: usize = 200;
+
+// Store file and key holding the typed app settings
+const APP_SETTINGS_STORE_FILE: &str = "app-settings.json";
+const APP_SETTINGS_KEY: &str = "settings";
+
+// Key in the app settings store holding the summary of the most recent scan
+const LAST_SCAN_KEY: &str = "last_scan";
+
+// Key in the app settings store holding the range of the most recent scan_network call
+const LAST_SCAN_PARAMS_KEY: &str = "last_scan_params";
+
+// Store file holding per-miner credentials, keyed by IP
+const MINER_AUTH_STORE_FILE: &str = "miner-auth.json";
+
+// Store file holding named tuning profiles, keyed by profile name
+const TUNING_PROFILES_STORE_FILE: &str = "tuning-profiles.json";
+
+// Store file holding scheduled settings changes, keyed by schedule id
+const SCHEDULE_STORE_FILE: &str = "scheduled-settings.json";
+
+// Store file holding user-assigned miner tags, keyed by IP
+const MINER_TAGS_STORE_FILE: &str = "miner-tags.json";
+
+// Store files flushed to disk on shutdown (including the ones the frontend loads)
+const STORE_FILES: &[&str] = &[
+    WINDOW_STORE_FILE,
+    APP_SETTINGS_STORE_FILE,
+    MINER_AUTH_STORE_FILE,
+    TUNING_PROFILES_STORE_FILE,
+    SCHEDULE_STORE_FILE,
+    MINER_TAGS_STORE_FILE,
+    "settings.json",
+    "miners.json",
+    "dashboard.json",
+    "benchmark-history.json",
+    "benchmark-profiles.json",
+];
+
+// How long shutdown waits for background tasks to stop
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+
+// Event a window's page emits (with its label) once it has loaded, and how long to wait for it
+const WINDOW_READY_EVENT: &str = "window-ready";
+const WINDOW_READY_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Secondary windows recreated by relayout_windows, and how long to wait for each to close
+const SECONDARY_WINDOWS: &[&str] = &["analytics", "settings", "tools", "benchmark"];
+const WINDOW_CLOSE_TIMEOUT: Duration = Duration::from_secs(2);
+
+// Windows whose position and size are remembered between opens
+const GEOMETRY_WINDOWS: &[&str] = &["analytics", "settings", "tools", "benchmark"];
+
+// Poll interval for thermal protection
+const THERMAL_POLL_SECS: u64 = 5;
+
+// How often run_benchmark samples the miner during a step
+const BENCHMARK_SAMPLE_SECS: u64 = 5;
+
+// Autotune sweeps voltage in this step, and rejects settings hotter than the ceiling
+const AUTOTUNE_VOLTAGE_STEP_MV: u32 = 25;
+const AUTOTUNE_DEFAULT_MAX_TEMP_C: f64 = 65.0;
+
+// A miner must cool this far below the alert threshold before it can alert again
+const TEMP_ALERT_HYSTERESIS_C: f64 = 5.0;
+
+// Stall watchdog: poll interval, hashrate (GH/s) treated as zero, and minimum gap between restarts of one miner
+const STALL_POLL_SECS: u64 = 15;
+const STALL_HASHRATE_EPSILON_GHS: f64 = 1.0;
+const STALL_RESTART_COOLDOWN: Duration = Duration::from_secs(30 * 60);
+
+// Latency samples kept per miner for latency_stats (oldest are dropped first)
+const LATENCY_SAMPLE_CAPACITY: usize = 512;
+
+// How often the settings scheduler checks for due changes
+const SCHEDULER_POLL_SECS: u64 = 15;
+
+// AxeOS /api/system fields patch_system may send (WiFi credentials are left out on purpose,
+// since a typo there takes the miner off the network)
+const PATCHABLE_SYSTEM_FIELDS: &[&str] = &[
+    "hostname",
+    "stratumURL",
+    "stratumPort",
+    "stratumUser",
+    "stratumPassword",
+    "stratumSuggestedDifficulty",
+    "stratumExtranonceSubscribe",
+    "fallbackStratumURL",
+    "fallbackStratumPort",
+    "fallbackStratumUser",
+    "fallbackStratumPassword",
+    "fallbackStratumSuggestedDifficulty",
+    "fallbackStratumExtranonceSubscribe",
+    "frequency",
+    "coreVoltage",
+    "overclockEnabled",
+    "autofanspeed",
+    "fanspeed",
+    "temptarget",
+    "overheat_mode",
+    "flipscreen",
+    "invertscreen",
+    "rotation",
+    "displayTimeout",
+    "statsFrequency",
+];
+
+// detect_conflicts: reads taken per IP and the pause between them
+const CONFLICT_SAMPLES: usize = 4;
+const CONFLICT_SAMPLE_DELAY_MS: u64 = 500;
+
+// How long update_and_verify waits before re-reading the miner
+const VERIFY_SETTLE_SECS: u64 = 3;
+
+// Largest response body accepted from a device while scanning
+const MAX_PROBE_BODY_BYTES: usize = 256 * 1024;
+
+// Websocket endpoint offered by AxeOS firmware that supports live streaming
+const MINER_WS_PATH: &str = "/api/ws";
+
+// Log endpoints tried by get_miner_log, and how many lines it returns by default
+const MINER_LOG_PATHS: &[&str] = &["/api/system/log", "/api/system/logs", "/api/log"];
+const DEFAULT_LOG_LINES: usize = 200;
+
+// WiFi reconnect endpoints tried by wifi_reconnect (not every AxeOS build has one)
+const WIFI_RECONNECT_PATHS: &[&str] = &["/api/system/wifi/reconnect", "/api/system/wifi/connect"];
+
+// AP mode endpoints tried by enter_ap_mode (stock AxeOS only falls back to AP after failed connects)
+const AP_MODE_PATHS: &[&str] = &["/api/system/wifi/ap", "/api/system/ap", "/api/system/apmode"];
+
+// Identify endpoints tried by identify_miner before it falls back to blinking the display
+const IDENTIFY_PATHS: &[&str] = &["/api/system/identify"];
+
+// Longest identify_miner blink, and the on/off period of the display fallback
+const MAX_IDENTIFY_SECS: u64 = 120;
+const IDENTIFY_BLINK_MS: u64 = 1000;
+
+// Interface name prefixes of docker/VM adapters ignored by get_all_local_subnets
+const VIRTUAL_INTERFACE_PREFIXES: &[&str] = &["docker", "br-", "veth", "virbr", "vmnet", "vboxnet", "vethernet", "utun"];
+
+// Service type browsed by discover_mdns (AxeOS advertises its web UI over HTTP)
+const MDNS_SERVICE_TYPE: &str = "_http._tcp.local.";
+
+// How long to wait for an mDNS answer when resolving a .local miner hostname
+const MDNS_RESOLVE_TIMEOUT_MS: u64 = 2000;
+
+// Timeout for ping_miner's single probe
+const PING_TIMEOUT_MS: u64 = 2000;
+
+// Timeout for test_pool's TCP connect
+const POOL_TEST_TIMEOUT_MS: u64 = 3000;
+
+// Timeout for the optional port-80 check made before each scan probe
+const SCAN_TCP_PREFILTER_TIMEOUT_MS: u64 = 300;
+
+// Temperature fields converted when Fahrenheit is requested (raw and normalized names)
+const TEMP_FIELDS: &[&str] = &["temp", "vrTemp", "vr_temp"];
+
+// Unit heuristics used when normalize_units is requested (raw and normalized names)
+// No AxeOS board draws more than this many watts, so a larger power reading is milliwatts
+const POWER_MILLIWATT_THRESHOLD: f64 = 1000.0;
+const POWER_FIELDS: &[&str] = &["power"];
+// Input voltage is 5-12 V, so a reading above this is millivolts
+const VOLTAGE_MILLIVOLT_THRESHOLD: f64 = 100.0;
+const VOLTAGE_FIELDS: &[&str] = &["voltage"];
+// ASIC core voltage is around 1.0-1.4 V, so a reading below this is volts rather than millivolts
+const CORE_VOLTAGE_VOLT_THRESHOLD: f64 = 10.0;
+const CORE_VOLTAGE_FIELDS: &[&str] = &["coreVoltage", "coreVoltageActual", "core_voltage", "core_voltage_actual"];
+
+// Shortest allowed polling interval for the background monitor
+const MIN_MONITOR_INTERVAL_SECS: u64 = 1;
+
+// Consecutive failed polls before the monitor reports a miner offline
+const PRESENCE_OFFLINE_AFTER_MISSES: u32 = 2;
+
+// How long get_miner_data reuses a fetch for the same IP (ms)
+const DEFAULT_DATA_CACHE_TTL_MS: u64 = 1000;
+
+// API paths tried in order when reading a miner, and the subset probed while scanning
+// (both can be replaced by the api_paths setting or a per-call paths argument)
+const DEFAULT_API_PATHS: &[&str] = &["/api/system/info", "/api/system", "/api/swarm/info"];
+const DEFAULT_PROBE_PATHS: &[&str] = &["/api/system/info", "/api/system"];
+
+// GitHub API endpoint for the latest esp-miner (AxeOS) release, and how long its answer is reused
+const FIRMWARE_RELEASES_URL: &str = "https://api.github.com/repos/bitaxeorg/ESP-Miner/releases/latest";
+const FIRMWARE_RELEASE_CACHE_TTL: Duration = Duration::from_secs(300);
+
+// Per-chip readings (hashrate in GH/s, temp in °C, frequency in MHz)
+#[derive(Debug, Clone, Serialize)]
+struct AsicChip {
+    index: usize,
+    hashrate: Option<f64>,
+    temp: Option<f64>,
+    frequency: Option<f64>,
+    error_count: Option<u64>,
+}
+
+// ASIC layout of a miner, returned by get_asic_details
+#[derive(Debug, Clone, Serialize)]
+struct AsicDetails {
+    model: Option<String>,
+    asic_count: usize,
+    small_core_count: Option<u64>,
+    chips: Vec<AsicChip>,
+}
+
+// WiFi details returned by get_network_status (rssi in dBm)
+#[derive(Debug, Clone, Serialize)]
+struct NetworkStatus {
+    rssi: Option<i32>,
+    ssid: Option<String>,
+    ap_mode: Option<bool>,
+    mac: Option<String>,
+}
+
+// Active pool returned by get_pool_status
+// connected is None when the firmware doesn't report the stratum connection state
+#[derive(Debug, Clone, Serialize)]
+struct PoolStatus {
+    url: Option<String>,
+    port: Option<u64>,
+    user: Option<String>,
+    using_fallback: bool,
+    connected: Option<bool>,
+}
+
+// Result of suggest_voltage: the curve's voltage for the frequency and a range worth trying
+#[derive(Debug, Clone, Serialize)]
+struct VoltageSuggestion {
+    suggested_mv: u32,
+    min_mv: u32,
+    max_mv: u32,
+}
+
+// An IP that answered as different devices across detect_conflicts reads
+#[derive(Debug, Clone, Serialize)]
+struct IpConflict {
+    ip: String,
+    macs: Vec<String>,
+    hostnames: Vec<String>,
+}
+
+// Share counters returned by get_share_stats (reject_rate is 0-1)
+#[derive(Debug, Clone, Serialize)]
+struct ShareStats {
+    accepted: u64,
+    rejected: u64,
+    reject_rate: f64,
+    best_diff: Option<String>,
+    session_diff: Option<String>,
+}
+
+// Uptime returned by get_uptime (since is the boot time as RFC 3339 UTC)
+#[derive(Debug, Clone, Serialize)]
+struct UptimeInfo {
+    uptime_secs: u64,
+    since: String,
+}
+
+// Result of test_pool
+#[derive(Debug, Clone, Serialize)]
+struct PoolTestResult {
+    resolvable: bool,
+    tcp_open: bool,
+    latency_ms: Option<u64>,
+}
+
+// Result of ping_miner
+#[derive(Debug, Clone, Serialize)]
+struct PingResult {
+    reachable: bool,
+    latency_ms: Option<u64>,
+}
+
+// Swarm-wide totals returned by get_swarm_summary (hashrate in GH/s, power in W)
+#[derive(Debug, Clone, Default, Serialize)]
+struct SwarmSummary {
+    total_hashrate: f64,
+    total_power: f64,
+    avg_temp: Option<f64>,
+    efficiency_j_th: Option<f64>,
+    miner_count: usize,
+    reachable_count: usize,
+}
+
+// Running electricity cost returned by estimate_cost (monthly is 30 days at the current draw)
+#[derive(Debug, Clone, Serialize)]
+struct CostEstimate {
+    total_watts: f64,
+    daily_cost: f64,
+    monthly_cost: f64,
+    price_per_kwh: f64,
+    currency: String,
+    reporting_count: usize,
+}
+
+// Per-miner outcome of restart_miners
+#[derive(Debug, Clone, Serialize)]
+struct RestartResult {
+    ip: String,
+    ok: bool,
+    error: Option<MinerError>,
+}
+
+// One miner's target setting for update_miner_settings_batch
+#[derive(Debug, Clone, Deserialize)]
+struct SettingsTarget {
+    ip: String,
+    frequency: u32,
+    core_voltage: u32,
+}
+
+// Named tuning profile applied with apply_profile (fan is a fixed percent; None means automatic)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TuningProfile {
+    frequency: u32,
+    core_voltage: u32,
+    fan: Option<u8>,
+}
+
+// Label and colour the user gave a miner (e.g. "rack A"), joined to miners by IP
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MinerTag {
+    label: String,
+    color: String,
+}
+
+// Settings change queued with schedule_settings, applied once at_unix has passed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ScheduledSettings {
+    id: u64,
+    ip: String,
+    at_unix: i64,
+    frequency: u32,
+    core_voltage: u32,
+}
+
+// Payload of the "scheduled-settings-applied" event
+#[derive(Debug, Clone, Serialize)]
+struct ScheduledSettingsApplied {
+    id: u64,
+    ip: String,
+    ok: bool,
+    error: Option<MinerError>,
+}
+
+// Result of update_and_verify: whether the miner reports the requested values after the change
+#[derive(Debug, Clone, Serialize)]
+struct SettingsVerification {
+    applied: bool,
+    actual_frequency: Option<f64>,
+    actual_voltage: Option<f64>,
+}
+
+// One miner's restorable settings in a swarm config file (pool passwords are not readable from AxeOS)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct MinerConfigSnapshot {
+    ip: String,
+    hostname: Option<String>,
+    stratum_url: Option<String>,
+    stratum_port: Option<u64>,
+    stratum_user: Option<String>,
+    fallback_stratum_url: Option<String>,
+    fallback_stratum_port: Option<u64>,
+    fallback_stratum_user: Option<String>,
+    frequency: Option<u64>,
+    core_voltage: Option<u64>,
+    auto_fan: Option<bool>,
+    fan_speed: Option<u64>,
+}
+
+impl MinerConfigSnapshot {
+    fn from_miner(ip: String, raw: &serde_json::Value) -> Self {
+        let info = normalize_miner_json(raw);
+        let text = |keys: &[&str]| field_alias(raw, keys).and_then(|v| v.as_str()).map(|s| s.to_string());
+        let integer = |keys: &[&str]| field_alias(raw, keys).and_then(|v| v.as_f64()).map(|n| n.max(0.0).round() as u64);
+        MinerConfigSnapshot {
+            ip,
+            hostname: info.hostname,
+            stratum_url: info.stratum_url,
+            stratum_port: info.stratum_port,
+            stratum_user: info.stratum_user,
+            fallback_stratum_url: text(&["fallbackStratumURL", "fallbackStratumUrl"]),
+            fallback_stratum_port: integer(&["fallbackStratumPort"]),
+            fallback_stratum_user: text(&["fallbackStratumUser"]),
+            frequency: info.frequency.map(|f| f.round() as u64),
+            core_voltage: info.core_voltage.map(|v| v.round() as u64),
+            auto_fan: field_alias(raw, &["autofanspeed"]).and_then(|v| v.as_bool().or_else(|| v.as_f64().map(|n| n != 0.0))),
+            fan_speed: info.fan_speed.map(|f| f.round() as u64),
+        }
+    }
+
+    // PATCH /api/system body restoring every field the snapshot has
+    fn patch_body(&self) -> serde_json::Map<String, serde_json::Value> {
+        let mut body = serde_json::Map::new();
+        let mut put = |key: &str, value: Option<serde_json::Value>| {
+            if let Some(value) = value {
+                body.insert(key.to_string(), value);
+            }
+        };
+        put("hostname", self.hostname.clone().map(Into::into));
+        put("stratumURL", self.stratum_url.clone().map(Into::into));
+        put("stratumPort", self.stratum_port.map(Into::into));
+        put("stratumUser", self.stratum_user.clone().map(Into::into));
+        put("fallbackStratumURL", self.fallback_stratum_url.clone().map(Into::into));
+        put("fallbackStratumPort", self.fallback_stratum_port.map(Into::into));
+        put("fallbackStratumUser", self.fallback_stratum_user.clone().map(Into::into));
+        put("frequency", self.frequency.map(Into::into));
+        put("coreVoltage", self.core_voltage.map(Into::into));
+        put("autofanspeed", self.auto_fan.map(|auto| u8::from(auto).into()));
+        // A fixed fan speed only matters with automatic control off
+        if self.auto_fan == Some(false) {
+            put("fanspeed", self.fan_speed.map(Into::into));
+        }
+        body
+    }
+}
+
+// Swarm config file written by export_swarm_config
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SwarmConfig {
+    exported_at: i64,
+    app_version: String,
+    miners: Vec<MinerConfigSnapshot>,
+}
+
+// Result of export_swarm_config: miners written to the file and those that couldn't be read
+#[derive(Debug, Clone, Serialize)]
+struct SwarmExportSummary {
+    path: String,
+    exported: usize,
+    failed: Vec<MinerDataResult>,
+}
+
+// Result of import_swarm_config: the file's miners, plus per-miner outcomes when applied
+#[derive(Debug, Clone, Serialize)]
+struct SwarmImportSummary {
+    exported_at: i64,
+    miners: Vec<MinerConfigSnapshot>,
+    results: Vec<SettingsResult>,
+}
+
+// Per-miner outcome of update_miner_settings_batch and update_pool_settings_batch (data is the miner's reply, or the preview on a dry run)
+#[derive(Debug, Clone, Serialize)]
+struct SettingsResult {
+    ip: String,
+    ok: bool,
+    data: Option<serde_json::Value>,
+    error: Option<MinerError>,
+}
+
+// Payload of the "ota-progress" event emitted during firmware uploads
+#[derive(Debug, Clone, Serialize)]
+struct OtaProgress {
+    ip: String,
+    sent: usize,
+    total: usize,
+}
+
+// Swarm health shown by the tray icon
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum TrayStatus {
+    Healthy,
+    Degraded,
+    Offline,
+}
+
+// Temperature unit preference
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+enum TempUnit {
+    #[default]
+    #[serde(rename = "C")]
+    Celsius,
+    #[serde(rename = "F")]
+    Fahrenheit,
+}
+
+// Credentials for miners behind an authenticating proxy or token-protected firmware
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum MinerAuth {
+    Basic { user: String, pass: String },
+    Bearer { token: String },
+}
+
+// App-wide settings kept in the store; missing keys take their defaults
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct AppSettings {
+    default_subnet: Option<String>,
+    scan_timeout_ms: u64,
+    poll_interval_secs: u64,
+    data_cache_ttl_ms: u64,
+    temp_unit: TempUnit,
+    theme: String,
+    close_to_tray: bool,
+    start_minimized: bool,
+    // Custom API paths for non-standard firmware; empty uses the built-in lists
+    api_paths: Vec<String>,
+    // Replaces DEFAULT_USER_AGENT on outgoing requests
+    user_agent: Option<String>,
+    // Electricity price used by estimate_cost
+    price_per_kwh: Option<f64>,
+    currency: String,
+    // Log every command with its duration in release builds too (logging starts on next launch)
+    verbose_logging: bool,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        AppSettings {
+            default_subnet: None,
+            scan_timeout_ms: DEFAULT_SCAN_TIMEOUT_MS,
+            poll_interval_secs: 15,
+            data_cache_ttl_ms: DEFAULT_DATA_CACHE_TTL_MS,
+            temp_unit: TempUnit::Celsius,
+            theme: "dark".to_string(),
+            close_to_tray: true,
+            start_minimized: false,
+            api_paths: Vec::new(),
+            user_agent: None,
+            price_per_kwh: None,
+            currency: "USD".to_string(),
+            verbose_logging: false,
+        }
+    }
+}
+
+// Build and platform details returned by get_app_info
+#[derive(Debug, Clone, Serialize)]
+struct AppInfo {
+    version: String,
+    tauri_version: String,
+    os: String,
+    arch: String,
+}
+
+// Saved position and size of a window, in logical pixels
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WindowGeometry {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+// Shared scan state, used to cancel in-progress scans
+// Each scan registers its own flag, so a cancel never leaks into a scan started after it
+#[derive(Default)]
+struct ScanState {
+    active: Mutex<Vec<Arc<AtomicBool>>>,
+}
+
+impl ScanState {
+    // Register a new scan; it stays cancellable until the returned token is dropped
+    fn begin(&self) -> ScanToken<'_> {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        if let Ok(mut active) = self.active.lock() {
+            active.push(cancelled.clone());
+        }
+        ScanToken { state: self, cancelled }
+    }
+
+    // Flag every running scan as cancelled
+    fn cancel_all(&self) {
+        if let Ok(active) = self.active.lock() {
+            for cancelled in active.iter() {
+                cancelled.store(true, Ordering::SeqCst);
+            }
+        }
+    }
+}
+
+// Cancel flag of one running scan, unregistered from ScanState when dropped
+struct ScanToken<'a> {
+    state: &'a ScanState,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl ScanToken<'_> {
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for ScanToken<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut active) = self.state.active.lock() {
+            active.retain(|cancelled| !Arc::ptr_eq(cancelled, &self.cancelled));
+        }
+    }
+}
+
+// Handle to the background monitoring task, if one is running
+#[derive(Default)]
+struct MonitorState {
+    task: Mutex<Option<JoinHandle<()>>>,
+    // Latest poll result per monitored miner, served by snapshot
+    latest: Mutex<HashMap<String, MonitorSnapshotEntry>>,
+}
+
+// A miner's most recent monitor poll and when it was taken (unix seconds)
+#[derive(Debug, Clone, Serialize)]
+struct MonitorSnapshotEntry {
+    #[serde(flatten)]
+    result: MinerDataResult,
+    updated_at: i64,
+}
+
+// Response times (ms) of successful monitor polls per miner, for latency_stats
+#[derive(Default)]
+struct LatencyState {
+    samples: Mutex<HashMap<String, VecDeque<u64>>>,
+}
+
+// Latency percentiles returned by latency_stats, in ms (all 0 when count is 0)
+#[derive(Debug, Clone, Default, Serialize)]
+struct LatencyStats {
+    p50: u64,
+    p95: u64,
+    max: u64,
+    count: usize,
+}
+
+// Payload of the "miner-presence" event: miners that came online or went offline this cycle
+#[derive(Debug, Clone, Default, Serialize)]
+struct PresenceDelta {
+    online: Vec<String>,
+    offline: Vec<String>,
+}
+
+// Handle to the temperature watchdog task, if one is running
+#[derive(Default)]
+struct TempWatchdogState {
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+// Windows in kiosk mode, with the geometry and decorations to restore when it ends
+#[derive(Default)]
+struct KioskState {
+    windows: Mutex<HashMap<String, KioskRestore>>,
+}
+
+// What a window looked like before kiosk mode
+#[derive(Debug, Clone, Copy)]
+struct KioskRestore {
+    position: tauri::PhysicalPosition<i32>,
+    size: tauri::PhysicalSize<u32>,
+    decorated: bool,
+}
+
+// Handle to the stall watchdog task, if one is running
+#[derive(Default)]
+struct StallWatchdogState {
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+// Global switch that makes the background polling loops skip their rounds (set_paused)
+#[derive(Default)]
+struct PauseState {
+    paused: AtomicBool,
+}
+
+// Handle to the settings scheduler task, started in setup
+#[derive(Default)]
+struct SchedulerState {
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+// Payload of the "stall-restart" event
+#[derive(Debug, Clone, Serialize)]
+struct StallRestartEvent {
+    ip: String,
+    stalled_secs: u64,
+    ok: bool,
+}
+
+// Thermal protection tasks and the pre-throttle settings of throttled miners
+// A throttled miner whose settings couldn't be read is kept with None, so it isn't throttled again
+#[derive(Default)]
+struct ThermalProtectionState {
+    tasks: Mutex<HashMap<String, JoinHandle<()>>>,
+    saved: Mutex<HashMap<String, Option<MinerSettingsUpdate>>>,
+}
+
+// Payload of the "thermal-throttle" event
+#[derive(Debug, Clone, Serialize)]
+struct ThermalThrottleEvent {
+    ip: String,
+    temp: f64,
+    frequency: u32,
+    core_voltage: u32,
+}
+
+// Cancel flag for a running autotune
+#[derive(Default)]
+struct AutotuneState {
+    cancelled: AtomicBool,
+}
+
+// Readings taken after a setting has settled
+#[derive(Debug, Clone, Serialize)]
+struct TuneMeasurement {
+    hashrate: f64,
+    power: f64,
+    temp: Option<f64>,
+    efficiency_j_th: Option<f64>,
+}
+
+// Payload of the "autotune-progress" event
+#[derive(Debug, Clone, Serialize)]
+struct AutotuneProgress {
+    step: usize,
+    total: usize,
+    frequency: u32,
+    core_voltage: u32,
+    measurement: TuneMeasurement,
+}
+
+// Winning setting returned by autotune
+#[derive(Debug, Clone, Serialize)]
+struct AutotuneResult {
+    frequency: u32,
+    core_voltage: u32,
+    measurement: TuneMeasurement,
+}
+
+// Cancel flag for a running benchmark
+#[derive(Default)]
+struct BenchmarkState {
+    cancelled: AtomicBool,
+}
+
+// One benchmark step: the setting to apply and how long to sample it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BenchStep {
+    frequency: u32,
+    core_voltage: u32,
+    dwell_secs: u64,
+}
+
+// Payload of the "benchmark-sample" event
+#[derive(Debug, Clone, Serialize)]
+struct BenchmarkSample {
+    ip: String,
+    step: usize,
+    elapsed_secs: u64,
+    measurement: TuneMeasurement,
+}
+
+// Averaged readings for one benchmark step
+#[derive(Debug, Clone, Serialize)]
+struct BenchStepResult {
+    frequency: u32,
+    core_voltage: u32,
+    avg_hashrate: f64,
+    avg_power: f64,
+    avg_temp: Option<f64>,
+    efficiency_j_th: Option<f64>,
+}
+
+// Result of run_benchmark
+#[derive(Debug, Clone, Serialize)]
+struct BenchmarkSummary {
+    ip: String,
+    cancelled: bool,
+    results: Vec<BenchStepResult>,
+    best_hashrate: Option<BenchStepResult>,
+    best_efficiency: Option<BenchStepResult>,
+}
+
+// Tells background loops to stop when the app exits, and tracks event writes still in flight
+// so shutdown can let them finish before the database closes
+struct ShutdownState {
+    signal: tokio::sync::watch::Sender<bool>,
+    writes: Mutex<tokio::task::JoinSet<()>>,
+}
+
+impl Default for ShutdownState {
+    fn default() -> Self {
+        Self {
+            signal: tokio::sync::watch::channel(false).0,
+            writes: Mutex::new(tokio::task::JoinSet::new()),
+        }
+    }
+}
+
+// Receiver that flips to true once shutdown starts
+fn shutdown_signal(app: &AppHandle) -> tokio::sync::watch::Receiver<bool> {
+    app.state::<ShutdownState>().signal.subscribe()
+}
+
+// Wait for a background loop's next tick; false once shutdown has started
+async fn next_tick(ticker: &mut tokio::time::Interval, shutdown: &mut tokio::sync::watch::Receiver<bool>) -> bool {
+    tokio::select! {
+        _ = ticker.tick() => {}
+        _ = shutdown.wait_for(|stopping| *stopping) => return false,
+    }
+    !*shutdown.borrow()
+}
+
+// Open websocket subscriptions, keyed by miner IP
+#[derive(Default)]
+struct WsState {
+    connections: Mutex<HashMap<String, JoinHandle<()>>>,
+}
+
+// App-wide cap on in-flight scan probes, discovery checks and background polls, so a scan during
+// monitoring doesn't flood the network; tokio's semaphore is FIFO, so neither side starves the other
+struct RequestBudget {
+    permits: tokio::sync::Semaphore,
+}
+
+impl Default for RequestBudget {
+    fn default() -> Self {
+        RequestBudget {
+            permits: tokio::sync::Semaphore::new(GLOBAL_REQUEST_BUDGET),
+        }
+    }
+}
+
+// Most recent get_miner_data response per IP, so several windows polling one miner share a fetch
+#[derive(Default)]
+struct MinerDataCache {
+    entries: tokio::sync::Mutex<HashMap<String, (Instant, serde_json::Value)>>,
+}
+
+// Latest esp-miner release tag and when it was fetched
+// A tokio mutex so concurrent checks wait for one lookup instead of each calling GitHub
+#[derive(Default)]
+struct FirmwareReleaseCache {
+    latest: tokio::sync::Mutex<Option<(Instant, String)>>,
+}
+
+// Result of check_firmware_update
+#[derive(Debug, Clone, Serialize)]
+struct FirmwareUpdateInfo {
+    current: String,
+    latest: Option<String>,
+    update_available: bool,
+}
+
+// Per-miner fetch outcome, used by the "miner-update" event and batch fetches
+#[derive(Debug, Clone, Serialize)]
+struct MinerDataResult {
+    ip: String,
+    data: Option<serde_json::Value>,
+    error: Option<MinerError>,
+}
+
+impl MinerDataResult {
+    fn new(ip: String, result: Result<serde_json::Value, MinerError>) -> Self {
+        match result {
+            Ok(data) => MinerDataResult { ip, data: Some(data), error: None },
+            Err(e) => MinerDataResult { ip, data: None, error: Some(e) },
+        }
+    }
+}
+
+// Kinds of entries in the events table
+#[derive(Debug, Clone, Copy)]
+enum MinerEventKind {
+    Unreachable,
+    Recovered,
+    Restarted,
+    RestartFailed,
+    SettingsChanged,
+}
+
+impl MinerEventKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MinerEventKind::Unreachable => "unreachable",
+            MinerEventKind::Recovered => "recovered",
+            MinerEventKind::Restarted => "restarted",
+            MinerEventKind::RestartFailed => "restart_failed",
+            MinerEventKind::SettingsChanged => "settings_changed",
+        }
+    }
+}
+
+// A row of the events table, returned by query_events
+#[derive(Debug, Clone, Serialize)]
+struct MinerEvent {
+    id: i64,
+    ip: String,
+    timestamp: i64,
+    kind: String,
+    detail: Option<String>,
+}
+
+// Miners currently known to be unreachable, so "recovered" is logged once
+#[derive(Default)]
+struct EventState {
+    unreachable: Mutex<HashSet<String>>,
+}
+
+// One bucket of efficiency_series: bucket start (unix seconds) and its J/TH
+#[derive(Debug, Clone, Serialize)]
+struct EfficiencyPoint {
+    t: i64,
+    j_per_th: f64,
+}
+
+// min/max/avg of one sample column; all null when no sample in range has a value
+#[derive(Debug, Clone, Serialize)]
+struct Rollup {
+    min: Option<f64>,
+    max: Option<f64>,
+    avg: Option<f64>,
+}
+
+// Result of sample_rollup
+#[derive(Debug, Clone, Serialize)]
+struct SampleRollup {
+    hashrate: Rollup,
+    temp: Rollup,
+    power: Rollup,
+}
+
+// Row shape of the samples table as read back by query_samples
+type SampleRow = (
+    i64,
+    Option<f64>,
+    Option<f64>,
+    Option<f64>,
+    Option<f64>,
+    Option<f64>,
+    Option<i64>,
+    Option<i64>,
+);
+
+// Row shape of the miners table as read back by load_saved_miners
+type SavedMinerRow = (String, Option<String>, Option<String>, Option<String>, i64);
+
+// SQLite pool for the app database (same axeos_data.db file the frontend uses)
+// None when it couldn't be opened; the app then runs without persisted history
+struct Database {
+    pool: Option<SqlitePool>,
+}
+
+impl Database {
+    fn pool(&self) -> Result<&SqlitePool, String> {
+        self.pool
+            .as_ref()
+            .ok_or_else(|| "The app database could not be opened, so history is unavailable".to_string())
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ScanProgress {
+    checked: usize,
+    total: usize,
+    found: usize,
+}
+
+// Which pass of a multi-pass scan a scan_ips run is, so "scan-progress" counts across all passes
+#[derive(Debug, Clone, Copy)]
+struct ScanPass {
+    index: usize,
+    count: usize,
+}
+
+impl ScanPass {
+    const SINGLE: ScanPass = ScanPass { index: 0, count: 1 };
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct MinerSettingsUpdate {
     frequency: u32,
-    #[serde(rename = "coreVoltage")]
+    #[serde(rename = "coreVoltage")]
+    core_voltage: u32,
+}
+
+// Error returned by commands that talk to miners
+// Serializes as {"kind": ..., "status": ..., "message": ...} so the UI can tell
+// an offline miner from a bad request, while "message" stays human readable.
+// Rejected settings also carry the miner's response body and the JSON that was sent
+#[derive(Debug, Clone)]
+enum MinerError {
+    Timeout,
+    Unreachable,
+    HttpStatus(u16),
+    Rejected {
+        status: u16,
+        body: String,
+        sent: serde_json::Value,
+    },
+    ParseError,
+    InvalidInput(String),
+    Unsupported(String),
+    Cancelled,
+    Internal(String),
+}
+
+impl MinerError {
+    fn kind(&self) -> &'static str {
+        match self {
+            MinerError::Timeout => "timeout",
+            MinerError::Unreachable => "unreachable",
+            MinerError::HttpStatus(_) => "http_status",
+            MinerError::Rejected { .. } => "rejected",
+            MinerError::ParseError => "parse_error",
+            MinerError::InvalidInput(_) => "invalid_input",
+            MinerError::Unsupported(_) => "unsupported",
+            MinerError::Cancelled => "cancelled",
+            MinerError::Internal(_) => "internal",
+        }
+    }
+}
+
+impl std::fmt::Display for MinerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MinerError::Timeout => write!(f, "Request to miner timed out"),
+            MinerError::Unreachable => write!(f, "Miner is unreachable"),
+            MinerError::HttpStatus(status) => write!(f, "Miner returned HTTP status {}", status),
+            MinerError::Rejected { status, body, .. } if body.trim().is_empty() => {
+                write!(f, "Miner rejected the request (HTTP {})", status)
+            }
+            MinerError::Rejected { status, body, .. } => {
+                write!(f, "Miner rejected the request (HTTP {}): {}", status, body.trim())
+            }
+            MinerError::ParseError => write!(f, "Miner returned an invalid response"),
+            MinerError::InvalidInput(message) => write!(f, "{}", message),
+            MinerError::Unsupported(message) => write!(f, "{}", message),
+            MinerError::Cancelled => write!(f, "Operation cancelled"),
+            MinerError::Internal(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for MinerError {}
+
+impl Serialize for MinerError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let status = match self {
+            MinerError::HttpStatus(status) | MinerError::Rejected { status, .. } => Some(*status),
+            _ => None,
+        };
+
+        let rejected = match self {
+            MinerError::Rejected { body, sent, .. } => Some((body, sent)),
+            _ => None,
+        };
+        let len = if rejected.is_some() { 5 } else { 3 };
+
+        let mut state = serializer.serialize_struct("MinerError", len)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("status", &status)?;
+        state.serialize_field("message", &self.to_string())?;
+        if let Some((body, sent)) = rejected {
+            state.serialize_field("body", body)?;
+            state.serialize_field("sent", sent)?;
+        }
+        state.end()
+    }
+}
+
+impl From<reqwest::Error> for MinerError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            MinerError::Timeout
+        } else if e.is_connect() {
+            MinerError::Unreachable
+        } else if e.is_decode() {
+            MinerError::ParseError
+        } else if let Some(status) = e.status() {
+            MinerError::HttpStatus(status.as_u16())
+        } else if e.is_builder() {
+            MinerError::Internal(e.to_string())
+        } else {
+            MinerError::Unreachable
+        }
+    }
+}
+
+// Lets String-returning commands use ? on miner helpers
+impl From<MinerError> for String {
+    fn from(e: MinerError) -> Self {
+        e.to_string()
+    }
+}
+
+// Safe sustained operating range for an ASIC model
+#[derive(Debug)]
+struct AsicSafeRange {
+    model: &'static str,
+    stock_frequency: u32,
+    stock_voltage: u32,
+    min_frequency: u32,
+    max_frequency: u32,
+    min_voltage: u32,
+    max_voltage: u32,
+}
+
+// Stock settings and known-safe ranges per ASIC (matches asic-presets.ts)
+const ASIC_SAFE_RANGES: &[AsicSafeRange] = &[
+    AsicSafeRange { model: "BM1370", stock_frequency: 525, stock_voltage: 1150, min_frequency: 400, max_frequency: 800, min_voltage: 1050, max_voltage: 1350 },
+    AsicSafeRange { model: "BM1368", stock_frequency: 490, stock_voltage: 1166, min_frequency: 400, max_frequency: 700, min_voltage: 1050, max_voltage: 1350 },
+    AsicSafeRange { model: "BM1366", stock_frequency: 485, stock_voltage: 1150, min_frequency: 400, max_frequency: 600, min_voltage: 1050, max_voltage: 1350 },
+];
+
+// Frequency (MHz) -> core voltage (mV) points for an ASIC, ascending by frequency
+#[derive(Debug)]
+struct AsicVoltageCurve {
+    model: &'static str,
+    points: &'static [(u32, u32)],
+}
+
+// Typical stable voltage per frequency from community tuning results; suggest_voltage
+// interpolates linearly between points and clamps to the ends of the curve
+const ASIC_VOLTAGE_CURVES: &[AsicVoltageCurve] = &[
+    AsicVoltageCurve { model: "BM1370", points: &[(400, 1080), (525, 1150), (600, 1200), (700, 1265), (800, 1340)] },
+    AsicVoltageCurve { model: "BM1368", points: &[(400, 1100), (490, 1166), (550, 1200), (600, 1230), (700, 1300)] },
+    AsicVoltageCurve { model: "BM1366", points: &[(400, 1100), (485, 1150), (525, 1200), (575, 1250), (600, 1270)] },
+];
+
+// Spread either side of the suggested voltage reported as min_mv/max_mv (chips vary)
+const VOLTAGE_SUGGESTION_MARGIN_MV: u32 = 50;
+
+// Community overclock preset, named "<frequency MHz>_<core voltage mV>"
+#[derive(Debug)]
+struct OverclockPreset {
+    name: &'static str,
+    frequency: u32,
+    core_voltage: u32,
+}
+
+// Built-in presets for apply_overclock_preset; each is still checked against the miner's ASIC
+const OVERCLOCK_PRESETS: &[OverclockPreset] = &[
+    OverclockPreset { name: "400_1150", frequency: 400, core_voltage: 1150 },
+    OverclockPreset { name: "401_1200", frequency: 401, core_voltage: 1200 },
+    OverclockPreset { name: "485_1150", frequency: 485, core_voltage: 1150 },
+    OverclockPreset { name: "485_1250", frequency: 485, core_voltage: 1250 },
+    OverclockPreset { name: "490_1166", frequency: 490, core_voltage: 1166 },
+    OverclockPreset { name: "525_1150", frequency: 525, core_voltage: 1150 },
+    OverclockPreset { name: "550_1200", frequency: 550, core_voltage: 1200 },
+    OverclockPreset { name: "575_1250", frequency: 575, core_voltage: 1250 },
+    OverclockPreset { name: "600_1150", frequency: 600, core_voltage: 1150 },
+    OverclockPreset { name: "625_1200", frequency: 625, core_voltage: 1200 },
+    OverclockPreset { name: "650_1250", frequency: 650, core_voltage: 1250 },
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PoolConfig {
+    url: String,
+    port: u32,
+    user: String,
+    password: String,
+}
+
+impl PoolConfig {
+    fn validate(&self) -> Result<(), MinerError> {
+        if self.url.trim().is_empty() {
+            return Err(MinerError::InvalidInput("Pool URL cannot be empty".to_string()));
+        }
+        if !(1..=65535).contains(&self.port) {
+            return Err(MinerError::InvalidInput(format!("Invalid pool port: {}. Must be between 1 and 65535", self.port)));
+        }
+        Ok(())
+    }
+}
+
+// Canonical miner data, normalized across AxeOS firmware versions
+#[derive(Debug, Clone, Default, Serialize)]
+struct MinerInfo {
+    hostname: Option<String>,
+    version: Option<String>,
+    asic_model: Option<String>,
+    board_version: Option<String>,
+    hash_rate: Option<f64>,
+    expected_hashrate: Option<f64>,
+    temp: Option<f64>,
+    vr_temp: Option<f64>,
+    power: Option<f64>,
+    voltage: Option<f64>,
+    current: Option<f64>,
+    frequency: Option<f64>,
+    core_voltage: Option<f64>,
+    core_voltage_actual: Option<f64>,
+    fan_speed: Option<f64>,
+    fan_rpm: Option<f64>,
+    shares_accepted: Option<u64>,
+    shares_rejected: Option<u64>,
+    uptime_seconds: Option<u64>,
+    best_diff: Option<String>,
+    best_session_diff: Option<String>,
+    stratum_url: Option<String>,
+    stratum_port: Option<u64>,
+    stratum_user: Option<String>,
+    wifi_rssi: Option<f64>,
+    ssid: Option<String>,
+    mac_addr: Option<String>,
+    ap_enabled: Option<bool>,
+    using_fallback_stratum: Option<bool>,
+    stratum_connected: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DiscoveredMiner {
+    ip: String,
+    #[serde(default)]
+    resolved_ip: Option<String>,
+    hostname: Option<String>,
+    version: Option<String>,
+    model: Option<String>,
+    hash_rate: Option<f64>,
+    temp: Option<f64>,
+    power: Option<f64>,
+    // Unix seconds when the miner was found (last seen, for saved miners)
+    #[serde(default)]
+    discovered_at: i64,
+}
+
+// A miner found by scan_network_thorough and how many of the passes it answered
+#[derive(Debug, Clone, Serialize)]
+struct ThoroughScanMiner {
+    #[serde(flatten)]
+    miner: DiscoveredMiner,
+    responded_passes: u32,
+    passes: u32,
+}
+
+// Range and timeout of the most recent scan_network call, for prefilling the scan form
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ScanParams {
+    subnet: String,
+    start: u8,
+    end: u8,
+    timeout_ms: u64,
+}
+
+// Summary of the most recent subnet/CIDR scan, returned by last_scan_info
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LastScanInfo {
+    at: i64,
+    count: usize,
+    subnet: String,
+}
+
+// Format a hashrate given in GH/s (as AxeOS reports it) with a readable unit
+fn format_hashrate(ghs: f64) -> String {
+    if ghs >= 1000.0 {
+        format!("{:.2} TH/s", ghs / 1000.0)
+    } else {
+        format!("{:.1} GH/s", ghs)
+    }
+}
+
+// Work out the swarm health shown by the tray icon from one monitoring cycle
+fn swarm_status(updates: &[MinerDataResult]) -> TrayStatus {
+    let mut status = TrayStatus::Healthy;
+    for update in updates {
+        let Some(data) = &update.data else {
+            return TrayStatus::Offline;
+        };
+        let number = |key: &str| data.get(key).and_then(|v| v.as_f64());
+
+        let hot = number("temp").is_some_and(|t| t >= DEGRADED_TEMP_C);
+        let rejecting = match (number("sharesAccepted"), number("sharesRejected")) {
+            (Some(accepted), Some(rejected)) if accepted + rejected > 0.0 => {
+                rejected / (accepted + rejected) > DEGRADED_REJECT_RATIO
+            }
+            _ => false,
+        };
+        if hot || rejecting {
+            status = TrayStatus::Degraded;
+        }
+    }
+    status
+}
+
+// Tray icon with a status dot drawn in the bottom-right corner
+fn tray_status_icon(status: TrayStatus) -> Result<Image<'static>, String> {
+    let base = Image::from_bytes(include_bytes!("../icons/32x32.png")).map_err(|e| e.to_string())?;
+    let (width, height) = (base.width(), base.height());
+    let mut rgba = base.rgba().to_vec();
+
+    let color: [u8; 3] = match status {
+        TrayStatus::Healthy => [34, 197, 94],
+        TrayStatus::Degraded => [234, 179, 8],
+        TrayStatus::Offline => [239, 68, 68],
+    };
+
+    let radius = width.min(height) as f64 / 5.0;
+    let (cx, cy) = (width as f64 - radius - 1.0, height as f64 - radius - 1.0);
+    for y in 0..height {
+        for x in 0..width {
+            let distance = ((x as f64 + 0.5 - cx).powi(2) + (y as f64 + 0.5 - cy).powi(2)).sqrt();
+            if distance > radius {
+                continue;
+            }
+            let i = ((y * width + x) * 4) as usize;
+            // Dark outline so the dot reads on light and dark taskbars
+            let pixel = if distance > radius - 1.0 { [0, 0, 0] } else { color };
+            rgba[i..i + 3].copy_from_slice(&pixel);
+            rgba[i + 3] = 255;
+        }
+    }
+
+    Ok(Image::new_owned(rgba, width, height))
+}
+
+// Host part of a URL for a miner address; IPv6 literals need brackets
+fn url_host(ip: &str) -> String {
+    if ip.parse::<Ipv6Addr>().is_ok() {
+        format!("[{}]", ip)
+    } else {
+        ip.to_string()
+    }
+}
+
+// HTTP URL for an API path on a miner (e.g. http://[fe80::1]/api/system)
+fn miner_url(ip: &str, path: &str) -> String {
+    format!("http://{}{}", url_host(ip), path)
+}
+
+// Whether a miner target is an mDNS name (e.g. bitaxe-garage.local)
+fn is_mdns_name(target: &str) -> bool {
+    target.trim_end_matches('.').to_ascii_lowercase().ends_with(".local")
+}
+
+// Check a miner target is an IP literal or a DNS name made of valid labels
+fn validate_miner_target(target: &str) -> Result<(), MinerError> {
+    if target.parse::<IpAddr>().is_ok() {
+        return Ok(());
+    }
+    let name = target.trim_end_matches('.');
+    if name.is_empty() || name.len() > 253 {
+        return Err(MinerError::InvalidInput(format!("Invalid miner address: {}", target)));
+    }
+    name.split('.').try_for_each(validate_hostname)
+}
+
+// Look up a .local name over mDNS, for hosts whose system resolver doesn't do mDNS
+async fn resolve_mdns_hostname(hostname: &str) -> Option<IpAddr> {
+    let mdns = ServiceDaemon::new().ok()?;
+    let fqdn = format!("{}.", hostname.trim_end_matches('.'));
+    let receiver = mdns.resolve_hostname(&fqdn, Some(MDNS_RESOLVE_TIMEOUT_MS)).ok()?;
+
+    let deadline = tokio::time::Instant::now() + Duration::from_millis(MDNS_RESOLVE_TIMEOUT_MS);
+    let mut found = None;
+    while let Ok(Ok(event)) = tokio::time::timeout_at(deadline, receiver.recv_async()).await {
+        if let HostnameResolutionEvent::AddressesFound(_, addrs) = event {
+            // Prefer IPv4, which is what AxeOS serves on
+            found = addrs.iter().find(|a| a.is_ipv4()).or_else(|| addrs.iter().next()).copied();
+            break;
+        }
+    }
+    let _ = mdns.shutdown();
+    found
+}
+
+// Resolve a miner target (IP literal or hostname) to an IP address
+// Hostnames go through the system resolver, falling back to mDNS for .local names
+async fn resolve_miner_host(target: &str) -> Option<String> {
+    if let Ok(addr) = target.parse::<IpAddr>() {
+        return Some(addr.to_string());
+    }
+
+    if let Ok(addrs) = tokio::net::lookup_host((target, 80)).await {
+        let addrs: Vec<IpAddr> = addrs.map(|a| a.ip()).collect();
+        if let Some(addr) = addrs.iter().find(|a| a.is_ipv4()).or_else(|| addrs.first()) {
+            return Some(addr.to_string());
+        }
+    }
+
+    if is_mdns_name(target) {
+        return resolve_mdns_hostname(target).await.map(|addr| addr.to_string());
+    }
+    None
+}
+
+// Host to put in a miner URL: the target itself, unless it's a .local name only mDNS can resolve
+async fn connect_host(target: &str) -> String {
+    if !is_mdns_name(target) || tokio::net::lookup_host((target, 80)).await.is_ok() {
+        return target.to_string();
+    }
+    match resolve_mdns_hostname(target).await {
+        Some(addr) => addr.to_string(),
+        None => target.to_string(),
+    }
+}
+
+// Client builder with the app's User-Agent; every outgoing HTTP client starts from this
+fn http_client_builder() -> reqwest::ClientBuilder {
+    let user_agent = USER_AGENT_OVERRIDE
+        .read()
+        .ok()
+        .and_then(|user_agent| user_agent.clone())
+        .unwrap_or_else(|| DEFAULT_USER_AGENT.to_string());
+    reqwest::Client::builder().user_agent(user_agent)
+}
+
+// Make the settings read outside an AppHandle (user_agent, verbose_logging) take effect
+fn apply_global_settings(settings: &AppSettings) {
+    VERBOSE_LOGGING.store(settings.verbose_logging, Ordering::SeqCst);
+    if let Ok(mut user_agent) = USER_AGENT_OVERRIDE.write() {
+        *user_agent = settings
+            .user_agent
+            .as_deref()
+            .map(str::trim)
+            .filter(|ua| !ua.is_empty())
+            .map(str::to_string);
+    }
+}
+
+// Logs a command's name, argument summary and duration when dropped
+// Active in debug builds and when the verbose_logging setting is on
+struct CommandTimer {
+    name: &'static str,
+    args: String,
+    started: Instant,
+    enabled: bool,
+}
+
+impl CommandTimer {
+    // Secrets (credentials, pool configs, raw field maps) are left out of the summaries by the callers
+    fn start(name: &'static str, args: impl FnOnce() -> String) -> Self {
+        let enabled = cfg!(debug_assertions) || VERBOSE_LOGGING.load(Ordering::SeqCst);
+        let args = if enabled {
+            let args = args();
+            match args.char_indices().nth(MAX_COMMAND_LOG_ARGS_CHARS) {
+                Some((end, _)) => format!("{}...", &args[..end]),
+                None => args,
+            }
+        } else {
+            String::new()
+        };
+        CommandTimer {
+            name,
+            args,
+            started: Instant::now(),
+            enabled,
+        }
+    }
+}
+
+impl Drop for CommandTimer {
+    fn drop(&mut self) {
+        if self.enabled {
+            log::info!("command {}({}) took {}ms", self.name, self.args, self.started.elapsed().as_millis());
+        }
+    }
+}
+
+// Current time as unix seconds
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+// Format unix seconds as an RFC 3339 UTC timestamp (e.g. 2024-05-01T12:00:00Z)
+fn format_unix_utc(secs: i64) -> String {
+    let days = secs.div_euclid(86_400);
+    let time = secs.rem_euclid(86_400);
+
+    // Civil date from days since 1970-01-01 (proleptic Gregorian)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+// Record a miner event in the events table without making the caller wait
+fn log_event(app: &AppHandle, ip: &str, kind: MinerEventKind, detail: impl Into<String>) {
+    let writer = app.clone();
+    let ip = ip.to_string();
+    let detail = detail.into();
+
+    let write = async move {
+        let Some(pool) = writer.try_state::<Database>().and_then(|db| db.pool.clone()) else {
+            return;
+        };
+        let inserted = sqlx::query("INSERT INTO events (ip, timestamp, kind, detail) VALUES (?, ?, ?, ?)")
+            .bind(&ip)
+            .bind(unix_now())
+            .bind(kind.as_str())
+            .bind(&detail)
+            .execute(&pool)
+            .await;
+        if let Err(e) = inserted {
+            log::error!("Failed to log {} event for {}: {}", kind.as_str(), ip, e);
+        }
+    };
+
+    // Tracked so shutdown can wait for it; finished writes are reaped as new ones start
+    let shutdown = app.state::<ShutdownState>();
+    let Ok(mut writes) = shutdown.writes.lock() else {
+        return;
+    };
+    while writes.try_join_next().is_some() {}
+    writes.spawn_on(write, tauri::async_runtime::handle().inner());
+}
+
+// Log "unreachable" when a miner stops answering and "recovered" when it comes back
+fn record_fetch_result<T>(app: &AppHandle, ip: &str, result: &Result<T, MinerError>) {
+    let Ok(mut unreachable) = app.state::<EventState>().inner().unreachable.lock() else {
+        return;
+    };
+    match result {
+        Err(e) => {
+            if unreachable.insert(ip.to_string()) {
+                log_event(app, ip, MinerEventKind::Unreachable, e.to_string());
+            }
+        }
+        Ok(_) => {
+            if unreachable.remove(ip) {
+                log_event(app, ip, MinerEventKind::Recovered, "");
+            }
+        }
+    }
+}
+
+// Log the outcome of a restart request
+fn record_restart_result<T>(app: &AppHandle, ip: &str, result: &Result<T, MinerError>) {
+    match result {
+        Ok(_) => log_event(app, ip, MinerEventKind::Restarted, ""),
+        Err(e) => log_event(app, ip, MinerEventKind::RestartFailed, e.to_string()),
+    }
+}
+
+// Open the app database and create backend-owned tables if they don't exist
+// WAL and a busy timeout let these writes and the frontend's SQL plugin connections share the file
+async fn init_database(app: &AppHandle) -> Result<SqlitePool, String> {
+    let dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let options = SqliteConnectOptions::new()
+        .filename(dir.join("axeos_data.db"))
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Wal)
+        .busy_timeout(Duration::from_secs(10));
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(4)
+        .connect_with(options)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS miners (
+           ip TEXT PRIMARY KEY,
+           hostname TEXT,
+           version TEXT,
+           model TEXT,
+           last_seen INTEGER NOT NULL
+         )",
+    )
+    .execute(&pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS samples (
+           id INTEGER PRIMARY KEY AUTOINCREMENT,
+           ip TEXT NOT NULL,
+           timestamp INTEGER NOT NULL,
+           hashrate REAL,
+           temp REAL,
+           power REAL,
+           frequency REAL,
+           core_voltage REAL,
+           shares_accepted INTEGER,
+           shares_rejected INTEGER
+         )",
+    )
+    .execute(&pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_samples_ip_timestamp ON samples(ip, timestamp)")
+        .execute(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS events (
+           id INTEGER PRIMARY KEY AUTOINCREMENT,
+           ip TEXT NOT NULL,
+           timestamp INTEGER NOT NULL,
+           kind TEXT NOT NULL,
+           detail TEXT
+         )",
+    )
+    .execute(&pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_events_ip_timestamp ON events(ip, timestamp)")
+        .execute(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    sqlx::query(
+        "CREATE TABLE IF NOT EXISTS benchmark_results (
+           id INTEGER PRIMARY KEY AUTOINCREMENT,
+           ip TEXT NOT NULL,
+           timestamp INTEGER NOT NULL,
+           frequency INTEGER NOT NULL,
+           core_voltage INTEGER NOT NULL,
+           avg_hashrate REAL NOT NULL,
+           avg_power REAL NOT NULL,
+           avg_temp REAL,
+           efficiency_j_th REAL
+         )",
+    )
+    .execute(&pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(pool)
+}
+
+// Attach miner credentials to a request, if any
+fn with_auth(request: reqwest::RequestBuilder, auth: Option<&MinerAuth>) -> reqwest::RequestBuilder {
+    match auth {
+        Some(MinerAuth::Basic { user, pass }) => request.basic_auth(user, Some(pass)),
+        Some(MinerAuth::Bearer { token }) => request.bearer_auth(token),
+        None => request,
+    }
+}
+
+// Credentials saved for a miner with set_miner_auth
+fn stored_miner_auth(app: &AppHandle, ip: &str) -> Option<MinerAuth> {
+    let store = app.store(MINER_AUTH_STORE_FILE).ok()?;
+    serde_json::from_value(store.get(ip)?).ok()
+}
+
+// The given credentials, else the ones saved for the miner with set_miner_auth
+fn resolve_miner_auth(app: &AppHandle, ip: &str, auth: Option<&MinerAuth>) -> Option<MinerAuth> {
+    auth.cloned().or_else(|| stored_miner_auth(app, ip))
+}
+
+// Command to save (or clear, with None) the credentials used for a miner
+#[tauri::command]
+async fn set_miner_auth(app: AppHandle, ip: String, auth: Option<MinerAuth>) -> Result<(), String> {
+    let _timer = CommandTimer::start("set_miner_auth", || format!("ip={:?}", ip));
+    let store = app.store(MINER_AUTH_STORE_FILE).map_err(|e| e.to_string())?;
+    match auth {
+        Some(auth) => store.set(ip, serde_json::to_value(&auth).map_err(|e| e.to_string())?),
+        None => {
+            store.delete(ip);
+        }
+    }
+    store.save().map_err(|e| e.to_string())
+}
+
+// Whether a colour is a #rgb or #rrggbb hex string
+fn is_hex_color(color: &str) -> bool {
+    color
+        .strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+// Command to tag a miner with a label and colour; an empty label removes the tag
+#[tauri::command]
+async fn set_miner_tag(app: AppHandle, ip: String, label: String, color: String) -> Result<(), String> {
+    let _timer = CommandTimer::start("set_miner_tag", || {
+        format!("ip={:?} label={:?} color={:?}", ip, label, color)
+    });
+    let store = app.store(MINER_TAGS_STORE_FILE).map_err(|e| e.to_string())?;
+
+    let label = label.trim();
+    if label.is_empty() {
+        store.delete(&ip);
+    } else {
+        let color = color.trim();
+        if !is_hex_color(color) {
+            return Err(format!("Invalid tag color: '{}'. Expected a hex color like #f7931a", color));
+        }
+        let tag = MinerTag {
+            label: label.to_string(),
+            color: color.to_string(),
+        };
+        store.set(ip, serde_json::to_value(&tag).map_err(|e| e.to_string())?);
+    }
+    store.save().map_err(|e| e.to_string())
+}
+
+// Command to get every miner tag, keyed by IP
+#[tauri::command]
+async fn get_miner_tags(app: AppHandle) -> Result<HashMap<String, MinerTag>, String> {
+    let _timer = CommandTimer::start("get_miner_tags", String::new);
+    let store = app.store(MINER_TAGS_STORE_FILE).map_err(|e| e.to_string())?;
+    let tags = store
+        .entries()
+        .into_iter()
+        .filter_map(|(ip, value)| Some((ip, serde_json::from_value(value).ok()?)))
+        .collect();
+    Ok(tags)
+}
+
+// API paths to use: the per-call list, else the api_paths setting, else the given defaults
+// Paths missing their leading slash get one
+fn resolve_api_paths(app: &AppHandle, paths: Option<Vec<String>>, defaults: &[&str]) -> Vec<String> {
+    let paths = paths
+        .filter(|paths| !paths.is_empty())
+        .unwrap_or_else(|| load_app_settings(app).api_paths);
+    if paths.is_empty() {
+        return defaults.iter().map(|path| path.to_string()).collect();
+    }
+    paths
+        .into_iter()
+        .map(|path| {
+            let path = path.trim();
+            if path.starts_with('/') {
+                path.to_string()
+            } else {
+                format!("/{}", path)
+            }
+        })
+        .collect()
+}
+
+// Fetch miner data, trying each API path from the api_paths setting (or the defaults) in order
+// Without explicit auth, the credentials saved for the miner are used
+async fn fetch_miner_data(
+    app: &AppHandle,
+    client: &reqwest::Client,
+    ip: &str,
+    max_retries: u32,
+    auth: Option<&MinerAuth>,
+) -> Result<serde_json::Value, MinerError> {
+    let auth = resolve_miner_auth(app, ip, auth);
+    let api_paths = resolve_api_paths(app, None, DEFAULT_API_PATHS);
+    fetch_miner_data_from(client, ip, max_retries, auth.as_ref(), &api_paths).await
+}
+
+// Fetch miner data, trying each of the given API paths in order
+// The path that succeeded is added to the JSON as "_source"
+// Connection/timeout errors are retried up to `max_retries` times per path with exponential backoff
+async fn fetch_miner_data_from<P: AsRef<str>>(
+    client: &reqwest::Client,
+    ip: &str,
+    max_retries: u32,
+    auth: Option<&MinerAuth>,
+    api_paths: &[P],
+) -> Result<serde_json::Value, MinerError> {
+    // Report the last failure seen, in case every path fails
+    let mut last_error = MinerError::Unreachable;
+    let host = connect_host(ip).await;
+
+    for path in api_paths {
+        let path = path.as_ref();
+        let url = miner_url(&host, path);
+        let mut attempt = 0;
+        loop {
+            match with_auth(client.get(&url), auth).send().await {
+                Ok(response) => {
+                    if response.status().is_success() {
+                        match response.json::<serde_json::Value>().await {
+                            Ok(mut json) => {
+                                // Tag the response with the endpoint that answered
+                                if let Some(obj) = json.as_object_mut() {
+                                    obj.insert("_source".to_string(), serde_json::Value::from(path));
+                                }
+                                return Ok(json);
+                            }
+                            Err(_) => last_error = MinerError::ParseError,
+                        }
+                    } else {
+                        last_error = MinerError::HttpStatus(response.status().as_u16());
+                    }
+                    // Got an HTTP answer, so move on to the next path without retrying
+                    break;
+                }
+                Err(e) if (e.is_connect() || e.is_timeout()) && attempt < max_retries => {
+                    let backoff = RETRY_BASE_DELAY_MS.saturating_mul(2u64.saturating_pow(attempt));
+                    tokio::time::sleep(Duration::from_millis(backoff)).await;
+                    attempt += 1;
+                }
+                Err(e) => {
+                    last_error = MinerError::from(e);
+                    break;
+                }
+            }
+        }
+    }
+
+    Err(last_error)
+}
+
+// Convert the temperature fields of an AxeOS response (reported in °C) to the given unit
+fn apply_temp_unit(data: &mut serde_json::Value, unit: TempUnit) {
+    if unit == TempUnit::Celsius {
+        return;
+    }
+    let Some(obj) = data.as_object_mut() else {
+        return;
+    };
+    for key in TEMP_FIELDS {
+        if let Some(celsius) = obj.get(*key).and_then(|v| v.as_f64()) {
+            obj.insert(key.to_string(), serde_json::Value::from(celsius * 9.0 / 5.0 + 32.0));
+        }
+    }
+}
+
+// Convert power/voltage fields to canonical units (W, V, and mV for core voltage)
+// Firmware versions disagree on units, so each value is classified by its magnitude
+// using the POWER_/VOLTAGE_/CORE_VOLTAGE_ thresholds above; in-range values are left as is
+fn apply_unit_normalization(data: &mut serde_json::Value) {
+    let Some(obj) = data.as_object_mut() else {
+        return;
+    };
+    let mut convert = |keys: &[&str], needs_conversion: fn(f64) -> bool, factor: f64| {
+        for key in keys {
+            if let Some(value) = obj.get(*key).and_then(|v| v.as_f64()) {
+                if needs_conversion(value) {
+                    obj.insert(key.to_string(), serde_json::Value::from(value * factor));
+                }
+            }
+        }
+    };
+    convert(POWER_FIELDS, |w| w > POWER_MILLIWATT_THRESHOLD, 0.001);
+    convert(VOLTAGE_FIELDS, |v| v > VOLTAGE_MILLIVOLT_THRESHOLD, 0.001);
+    convert(CORE_VOLTAGE_FIELDS, |mv| mv > 0.0 && mv < CORE_VOLTAGE_VOLT_THRESHOLD, 1000.0);
+}
+
+// Command to fetch miner data
+// max_retries defaults to 3; pass 0 to disable retrying
+// temp_unit "F" returns temp/vrTemp in Fahrenheit
+// normalized returns the canonical MinerInfo shape instead of the raw firmware JSON
+// auth overrides the credentials saved with set_miner_auth
+// Responses younger than the data_cache_ttl_ms setting are reused unless force is set;
+// calls with their own auth or paths skip the cache, since it holds responses fetched with the saved ones
+// normalize_units converts power/voltage to W/V and coreVoltage to mV (see apply_unit_normalization)
+// paths overrides the API paths tried (see resolve_api_paths)
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn get_miner_data(
+    app: AppHandle,
+    cache: State<'_, MinerDataCache>,
+    ip: String,
+    max_retries: Option<u32>,
+    temp_unit: Option<TempUnit>,
+    normalized: Option<bool>,
+    auth: Option<MinerAuth>,
+    force: Option<bool>,
+    normalize_units: Option<bool>,
+    paths: Option<Vec<String>>,
+) -> Result<serde_json::Value, MinerError> {
+    let _timer = CommandTimer::start("get_miner_data", || {
+        format!(
+            "ip={:?} max_retries={:?} temp_unit={:?} normalized={:?} force={:?} normalize_units={:?} paths={:?}",
+            ip, max_retries, temp_unit, normalized, force, normalize_units, paths
+        )
+    });
+    let ttl = Duration::from_millis(load_app_settings(&app).data_cache_ttl_ms);
+    let cacheable = auth.is_none() && paths.is_none();
+    let cached = if force.unwrap_or(false) || !cacheable {
+        None
+    } else {
+        cache
+            .entries
+            .lock()
+            .await
+            .get(&ip)
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < ttl)
+            .map(|(_, data)| data.clone())
+    };
+
+    let mut data = match cached {
+        Some(data) => data,
+        None => {
+            let client = http_client_builder()
+                .timeout(Duration::from_secs(10))
+                .build()?;
+
+            let auth = auth.or_else(|| stored_miner_auth(&app, &ip));
+            let api_paths = resolve_api_paths(&app, paths, DEFAULT_API_PATHS);
+            let retries = max_retries.unwrap_or(DEFAULT_FETCH_RETRIES);
+            let result = fetch_miner_data_from(&client, &ip, retries, auth.as_ref(), &api_paths).await;
+            record_fetch_result(&app, &ip, &result);
+
+            let data = result?;
+            if cacheable {
+                cache.entries.lock().await.insert(ip.clone(), (Instant::now(), data.clone()));
+            }
+            data
+        }
+    };
+    if normalized.unwrap_or(false) {
+        let source = data.get("_source").cloned();
+        data = serde_json::to_value(normalize_miner_json(&data)).map_err(|e| MinerError::Internal(e.to_string()))?;
+        if let (Some(obj), Some(source)) = (data.as_object_mut(), source) {
+            obj.insert("_source".to_string(), source);
+        }
+    }
+    if normalize_units.unwrap_or(false) {
+        apply_unit_normalization(&mut data);
+    }
+    apply_temp_unit(&mut data, temp_unit.unwrap_or_default());
+    Ok(data)
+}
+
+// Fetch several miners concurrently (bounded like the scan), one result per miner
+async fn fetch_many_miners(app: &AppHandle, ips: Vec<String>) -> Result<Vec<MinerDataResult>, MinerError> {
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let tasks = ips.into_iter().map(|ip| {
+        let client = &client;
+        async move {
+            let permit = app.state::<RequestBudget>().inner().permits.acquire().await.ok();
+            let result = fetch_miner_data(app, client, &ip, DEFAULT_FETCH_RETRIES, None).await;
+            drop(permit);
+            MinerDataResult::new(ip, result)
+        }
+    });
+
+    let results = stream::iter(tasks)
+        .buffered(DEFAULT_SCAN_CONCURRENCY)
+        .collect()
+        .await;
+
+    Ok(results)
+}
+
+// Command for a quick up/down check with round-trip latency
+// A single HEAD request; any HTTP answer counts as reachable
+#[tauri::command]
+async fn ping_miner(ip: String) -> Result<PingResult, MinerError> {
+    let _timer = CommandTimer::start("ping_miner", || format!("ip={:?}", ip));
+    let client = http_client_builder()
+        .timeout(Duration::from_millis(PING_TIMEOUT_MS))
+        .build()?;
+
+    let url = miner_url(&ip, "/");
+    let started = Instant::now();
+
+    let result = match client.head(&url).send().await {
+        Ok(_) => PingResult {
+            reachable: true,
+            latency_ms: Some(started.elapsed().as_millis() as u64),
+        },
+        Err(_) => PingResult {
+            reachable: false,
+            latency_ms: None,
+        },
+    };
+
+    Ok(result)
+}
+
+// Parse chip count and per-chip data from an AxeOS response
+// Single-chip boards (or firmware without per-chip data) yield one chip built from the top-level fields
+fn parse_asic_details(data: &serde_json::Value) -> AsicDetails {
+    let number = |value: Option<&serde_json::Value>| value.and_then(|v| v.as_f64());
+    let array = |key: &str| data.get(key).and_then(|v| v.as_array());
+    let monitor = data
+        .get("hashrateMonitor")
+        .and_then(|m| m.get("asics"))
+        .and_then(|v| v.as_array());
+    let temps = array("asicTemps");
+    let frequencies = array("asicFrequencies");
+
+    let reported_count = data.get("asicCount").and_then(|v| v.as_u64()).map(|n| n as usize);
+    let per_chip_len = [monitor, temps, frequencies]
+        .iter()
+        .filter_map(|a| a.map(|a| a.len()))
+        .max()
+        .unwrap_or(0);
+
+    let chips = if per_chip_len == 0 {
+        vec![AsicChip {
+            index: 0,
+            hashrate: number(data.get("hashRate")),
+            temp: number(data.get("temp")),
+            frequency: number(data.get("frequency")),
+            error_count: None,
+        }]
+    } else {
+        (0..per_chip_len)
+            .map(|index| {
+                let chip = monitor.and_then(|m| m.get(index));
+                AsicChip {
+                    index,
+                    hashrate: number(chip.and_then(|c| c.get("total"))),
+                    temp: number(temps.and_then(|t| t.get(index))),
+                    frequency: number(frequencies.and_then(|f| f.get(index))),
+                    error_count: chip.and_then(|c| c.get("errorCount")).and_then(|v| v.as_u64()),
+                }
+            })
+            .collect()
+    };
+
+    AsicDetails {
+        model: data.get("ASICModel").and_then(|v| v.as_str()).map(|s| s.to_string()),
+        asic_count: reported_count.unwrap_or(chips.len()),
+        small_core_count: data.get("smallCoreCount").and_then(|v| v.as_u64()),
+        chips,
+    }
+}
+
+// Parse a semver-ish firmware tag ("v2.4.1", "2.5", "v2.6.0-rc1", "2.4.1-dirty") into (major, minor, patch, is_release)
+// Pre-releases sort below the release of the same number
+fn parse_firmware_version(tag: &str) -> Option<(u64, u64, u64, bool)> {
+    let tag = tag.trim().trim_start_matches(['v', 'V']);
+    let numeric_len = tag.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(tag.len());
+    let (numeric, suffix) = tag.split_at(numeric_len);
+
+    let mut parts = numeric.split('.').filter(|p| !p.is_empty()).map(|p| p.parse::<u64>());
+    let major = parts.next()?.ok()?;
+    let minor = parts.next().transpose().ok()?.unwrap_or(0);
+    let patch = parts.next().transpose().ok()?.unwrap_or(0);
+
+    // Only explicit pre-release markers count; build suffixes like "-dirty" don't
+    let suffix = suffix.to_ascii_lowercase();
+    let is_release = !["rc", "alpha", "beta", "pre", "dev"].iter().any(|m| suffix.contains(m));
+
+    Some((major, minor, patch, is_release))
+}
+
+// Latest esp-miner release tag, served from the cache while it is fresh
+async fn latest_firmware_release(cache: &FirmwareReleaseCache) -> Result<String, MinerError> {
+    let mut latest = cache.latest.lock().await;
+    if let Some((fetched_at, tag)) = latest.as_ref() {
+        if fetched_at.elapsed() < FIRMWARE_RELEASE_CACHE_TTL {
+            return Ok(tag.clone());
+        }
+    }
+
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    // GitHub rejects API requests without a User-Agent (http_client_builder always sets one)
+    let response = client
+        .get(FIRMWARE_RELEASES_URL)
+        .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        return Err(MinerError::HttpStatus(response.status().as_u16()));
+    }
+
+    let release = response.json::<serde_json::Value>().await?;
+    let tag = release
+        .get("tag_name")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .ok_or(MinerError::ParseError)?;
+
+    *latest = Some((Instant::now(), tag.clone()));
+    Ok(tag)
+}
+
+// Command to compare a miner's firmware with the latest esp-miner release
+// If GitHub can't be reached, latest is None and no update is flagged
+#[tauri::command]
+async fn check_firmware_update(
+    app: AppHandle,
+    cache: State<'_, FirmwareReleaseCache>,
+    ip: String,
+) -> Result<FirmwareUpdateInfo, MinerError> {
+    let _timer = CommandTimer::start("check_firmware_update", || format!("ip={:?}", ip));
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let info = normalize_miner_json(&fetch_miner_data(&app, &client, &ip, DEFAULT_FETCH_RETRIES, None).await?);
+    let current = info.version.ok_or(MinerError::ParseError)?;
+
+    let latest = match latest_firmware_release(&cache).await {
+        Ok(tag) => Some(tag),
+        Err(e) => {
+            log::warn!("Failed to look up latest firmware release: {}", e);
+            None
+        }
+    };
+
+    let update_available = match (parse_firmware_version(&current), latest.as_deref().and_then(parse_firmware_version)) {
+        (Some(current), Some(latest)) => latest > current,
+        _ => false,
+    };
+
+    Ok(FirmwareUpdateInfo {
+        current,
+        latest,
+        update_available,
+    })
+}
+
+// Command to open a miner's web UI in the default browser
+// Only IP literals and valid hostnames are accepted so nothing else is handed to the shell
+#[tauri::command]
+#[allow(deprecated)]
+async fn open_miner_web(app: AppHandle, ip: String) -> Result<(), MinerError> {
+    let _timer = CommandTimer::start("open_miner_web", || format!("ip={:?}", ip));
+    let target = ip.trim();
+    validate_miner_target(target)?;
+
+    app.shell()
+        .open(miner_url(target, "/"), None)
+        .map_err(|e| MinerError::Internal(e.to_string()))
+}
+
+// Split a log response into lines: plaintext, a JSON array, or an object holding "logs"/"lines"
+// Array entries may be strings or objects with a "message"/"msg"/"line" field
+fn parse_log_lines(body: &str) -> Vec<String> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(body) else {
+        return body.lines().map(|line| line.trim_end().to_string()).filter(|line| !line.is_empty()).collect();
+    };
+
+    let entries = json
+        .as_array()
+        .or_else(|| field_alias(&json, &["logs", "lines", "log"]).and_then(|v| v.as_array()));
+    let Some(entries) = entries else {
+        // A single JSON string holding the whole log
+        return json.as_str().map(parse_log_lines).unwrap_or_default();
+    };
+
+    entries
+        .iter()
+        .filter_map(|entry| match entry {
+            serde_json::Value::String(s) => Some(s.clone()),
+            _ => field_alias(entry, &["message", "msg", "line"])?.as_str().map(|s| s.to_string()),
+        })
+        .collect()
+}
+
+// Command to get the last lines of the miner's own log
+// lines defaults to 200; firmware without an HTTP log endpoint returns an "unsupported" error
+#[tauri::command]
+async fn get_miner_log(app: AppHandle, ip: String, lines: Option<usize>) -> Result<Vec<String>, MinerError> {
+    let _timer = CommandTimer::start("get_miner_log", || format!("ip={:?} lines={:?}", ip, lines));
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let auth = stored_miner_auth(&app, &ip);
+    let host = connect_host(&ip).await;
+    for path in MINER_LOG_PATHS {
+        let response = with_auth(client.get(miner_url(&host, path)), auth.as_ref()).send().await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            continue;
+        }
+        if !response.status().is_success() {
+            return Err(MinerError::HttpStatus(response.status().as_u16()));
+        }
+
+        let body = response.text().await?;
+        let mut log = parse_log_lines(&body);
+        let keep = lines.unwrap_or(DEFAULT_LOG_LINES);
+        if log.len() > keep {
+            log.drain(..log.len() - keep);
+        }
+        return Ok(log);
+    }
+
+    Err(MinerError::Unsupported(format!(
+        "Miner at {} doesn't expose its log over HTTP. Its firmware may only stream logs to the web console",
+        ip
+    )))
+}
+
+// Diagnostics section: the value on success, or a note with the structured error
+fn diagnostics_section<T: Serialize>(result: Result<T, MinerError>) -> serde_json::Value {
+    match result.map(serde_json::to_value) {
+        Ok(Ok(value)) => value,
+        Ok(Err(e)) => serde_json::json!({ "error": e.to_string() }),
+        Err(e) => serde_json::json!({ "error": e }),
+    }
+}
+
+// Command to write a diagnostic bundle for a support request
+// System info, swarm info, network status and recent log go into one JSON file at out_path;
+// a section that can't be fetched records its error instead of failing the bundle
+#[tauri::command]
+async fn collect_diagnostics(app: AppHandle, ip: String, out_path: String) -> Result<String, MinerError> {
+    let _timer = CommandTimer::start("collect_diagnostics", || {
+        format!("ip={:?} out_path={:?}", ip, out_path)
+    });
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let swarm_info = async {
+        let auth = stored_miner_auth(&app, &ip);
+        let response = with_auth(client.get(miner_url(&connect_host(&ip).await, "/api/swarm/info")), auth.as_ref())
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(MinerError::HttpStatus(response.status().as_u16()));
+        }
+        Ok::<_, MinerError>(response.json::<serde_json::Value>().await?)
+    };
+
+    let (system_info, swarm_info, log) = future::join3(
+        fetch_miner_data(&app, &client, &ip, DEFAULT_FETCH_RETRIES, None),
+        swarm_info,
+        get_miner_log(app.clone(), ip.clone(), None),
+    )
+    .await;
+    // Network details come from the same system info rather than a second fetch
+    let network_status = system_info.as_ref().map(network_status_from).map_err(MinerError::clone);
+
+    let bundle = serde_json::json!({
+        "ip": ip,
+        "timestamp": unix_now(),
+        "app_version": env!("CARGO_PKG_VERSION"),
+        "system_info": diagnostics_section(system_info),
+        "swarm_info": diagnostics_section(swarm_info),
+        "network_status": diagnostics_section(network_status),
+        "log": diagnostics_section(log),
+    });
+
+    let contents = serde_json::to_string_pretty(&bundle).map_err(|e| MinerError::Internal(e.to_string()))?;
+    tokio::fs::write(&out_path, contents)
+        .await
+        .map_err(|e| MinerError::InvalidInput(format!("Failed to write {}: {}", out_path, e)))?;
+
+    Ok(out_path)
+}
+
+// Command to get the miner's WiFi signal strength and network details
+#[tauri::command]
+async fn get_network_status(app: AppHandle, ip: String) -> Result<NetworkStatus, MinerError> {
+    let _timer = CommandTimer::start("get_network_status", || format!("ip={:?}", ip));
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let data = fetch_miner_data(&app, &client, &ip, DEFAULT_FETCH_RETRIES, None).await?;
+    Ok(network_status_from(&data))
+}
+
+// Pick the WiFi signal and network details out of /api/system/info
+fn network_status_from(data: &serde_json::Value) -> NetworkStatus {
+    let info = normalize_miner_json(data);
+    NetworkStatus {
+        rssi: info.wifi_rssi.map(|rssi| rssi.round() as i32),
+        ssid: info.ssid,
+        ap_mode: info.ap_enabled,
+        mac: info.mac_addr,
+    }
+}
+
+// Command to get the pool the miner is currently using and whether it's connected to it
+// Reports the fallback pool while the miner has failed over to it
+#[tauri::command]
+async fn get_pool_status(app: AppHandle, ip: String) -> Result<PoolStatus, MinerError> {
+    let _timer = CommandTimer::start("get_pool_status", || format!("ip={:?}", ip));
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let raw = fetch_miner_data(&app, &client, &ip, DEFAULT_FETCH_RETRIES, None).await?;
+    let info = normalize_miner_json(&raw);
+    let using_fallback = info.using_fallback_stratum.unwrap_or(false);
+
+    let (url, port, user) = if using_fallback {
+        let text = |keys: &[&str]| field_alias(&raw, keys).and_then(|v| v.as_str()).map(|s| s.to_string());
+        (
+            text(&["fallbackStratumURL", "fallbackStratumUrl"]),
+            field_alias(&raw, &["fallbackStratumPort"]).and_then(|v| v.as_u64()),
+            text(&["fallbackStratumUser"]),
+        )
+    } else {
+        (info.stratum_url, info.stratum_port, info.stratum_user)
+    };
+
+    Ok(PoolStatus {
+        url,
+        port,
+        user,
+        using_fallback,
+        connected: info.stratum_connected,
+    })
+}
+
+// Command to find IP conflicts: each IP is read several times, and one whose MAC address or
+// hostname changes between reads is shared by more than one device
+#[tauri::command]
+async fn detect_conflicts(app: AppHandle, ips: Vec<String>) -> Result<Vec<IpConflict>, MinerError> {
+    let _timer = CommandTimer::start("detect_conflicts", || format!("ips={:?}", ips));
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let checks = ips.into_iter().map(|ip| {
+        let client = &client;
+        let app = &app;
+        async move {
+            let mut macs: Vec<String> = Vec::new();
+            let mut hostnames: Vec<String> = Vec::new();
+            for sample in 0..CONFLICT_SAMPLES {
+                if sample > 0 {
+                    tokio::time::sleep(Duration::from_millis(CONFLICT_SAMPLE_DELAY_MS)).await;
+                }
+                let Ok(data) = fetch_miner_data(app, client, &ip, 0, None).await else {
+                    continue;
+                };
+                let info = normalize_miner_json(&data);
+                if let Some(mac) = info.mac_addr.map(|mac| mac.to_uppercase()) {
+                    if !macs.contains(&mac) {
+                        macs.push(mac);
+                    }
+                }
+                if let Some(hostname) = info.hostname {
+                    if !hostnames.contains(&hostname) {
+                        hostnames.push(hostname);
+                    }
+                }
+            }
+            (macs.len() > 1 || hostnames.len() > 1).then_some(IpConflict { ip, macs, hostnames })
+        }
+    });
+
+    let conflicts = stream::iter(checks)
+        .buffered(DEFAULT_SCAN_CONCURRENCY)
+        .filter_map(future::ready)
+        .collect()
+        .await;
+    Ok(conflicts)
+}
+
+// Command to make the miner drop and re-associate its WiFi, e.g. to move to a closer access point
+// The miner is unreachable for a few seconds afterwards; check get_network_status for the new RSSI.
+// Firmware without a reconnect endpoint returns an "unsupported" error
+#[tauri::command]
+async fn wifi_reconnect(app: AppHandle, ip: String) -> Result<serde_json::Value, MinerError> {
+    let _timer = CommandTimer::start("wifi_reconnect", || format!("ip={:?}", ip));
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let auth = stored_miner_auth(&app, &ip);
+    let host = connect_host(&ip).await;
+    match post_first_supported(&client, &host, WIFI_RECONNECT_PATHS, auth.as_ref()).await? {
+        Some(response) => action_response_json(response).await,
+        None => Err(MinerError::Unsupported(format!(
+            "Miner at {} has no WiFi reconnect endpoint. Restart it to make it re-associate",
+            ip
+        ))),
+    }
+}
+
+// Command to force the miner into its access point fallback, e.g. after a router change left
+// its saved WiFi credentials stale. The miner leaves the network and must be reconfigured
+// from its own AP. Firmware without an AP mode endpoint returns an "unsupported" error
+#[tauri::command]
+async fn enter_ap_mode(app: AppHandle, ip: String) -> Result<serde_json::Value, MinerError> {
+    let _timer = CommandTimer::start("enter_ap_mode", || format!("ip={:?}", ip));
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let auth = stored_miner_auth(&app, &ip);
+    let host = connect_host(&ip).await;
+    match post_first_supported(&client, &host, AP_MODE_PATHS, auth.as_ref()).await {
+        Ok(Some(response)) => action_response_json(response).await,
+        Ok(None) => Err(MinerError::Unsupported(format!(
+            "Miner at {} has no AP mode endpoint. Older AxeOS only starts its AP after failing to join the saved WiFi",
+            ip
+        ))),
+        // The miner may drop off WiFi before it answers; any other failure means the request never landed
+        Err(e) if e.is_timeout() || closed_after_request(&e) => {
+            log::warn!("No response from {} after requesting AP mode: {}", ip, e);
+            Ok(serde_json::json!({ "success": true }))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+// Whether the connection closed after the request was sent but before any response arrived
+fn closed_after_request(error: &reqwest::Error) -> bool {
+    let mut source = std::error::Error::source(error);
+    while let Some(err) = source {
+        if let Some(hyper_error) = err.downcast_ref::<hyper::Error>() {
+            return hyper_error.is_incomplete_message();
+        }
+        source = err.source();
+    }
+    false
+}
+
+// Command to get the miner's share counts and reject rate
+// reject_rate is 0 until the miner has submitted a share
+#[tauri::command]
+async fn get_share_stats(app: AppHandle, ip: String) -> Result<ShareStats, MinerError> {
+    let _timer = CommandTimer::start("get_share_stats", || format!("ip={:?}", ip));
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let info = normalize_miner_json(&fetch_miner_data(&app, &client, &ip, DEFAULT_FETCH_RETRIES, None).await?);
+    let accepted = info.shares_accepted.unwrap_or(0);
+    let rejected = info.shares_rejected.unwrap_or(0);
+    let total = accepted + rejected;
+    let reject_rate = if total > 0 { rejected as f64 / total as f64 } else { 0.0 };
+
+    Ok(ShareStats {
+        accepted,
+        rejected,
+        reject_rate,
+        best_diff: info.best_diff,
+        session_diff: info.best_session_diff,
+    })
+}
+
+// Command to get how long the miner has been running and when it last booted
+#[tauri::command]
+async fn get_uptime(app: AppHandle, ip: String) -> Result<UptimeInfo, MinerError> {
+    let _timer = CommandTimer::start("get_uptime", || format!("ip={:?}", ip));
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let info = normalize_miner_json(&fetch_miner_data(&app, &client, &ip, DEFAULT_FETCH_RETRIES, None).await?);
+    let uptime_secs = info.uptime_seconds.ok_or(MinerError::ParseError)?;
+
+    Ok(UptimeInfo {
+        uptime_secs,
+        since: format_unix_utc(unix_now() - uptime_secs as i64),
+    })
+}
+
+// Command to get the miner's ASIC chip count and per-chip data
+#[tauri::command]
+async fn get_asic_details(app: AppHandle, ip: String) -> Result<AsicDetails, MinerError> {
+    let _timer = CommandTimer::start("get_asic_details", || format!("ip={:?}", ip));
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let data = fetch_miner_data(&app, &client, &ip, DEFAULT_FETCH_RETRIES, None).await?;
+    Ok(parse_asic_details(&data))
+}
+
+// Command to fetch several miners in one call
+// Each miner gets its own result, so one failure doesn't fail the batch
+#[tauri::command]
+async fn get_miner_data_batch(
+    app: AppHandle,
+    ips: Vec<String>,
+    temp_unit: Option<TempUnit>,
+    normalize_units: Option<bool>,
+) -> Result<Vec<MinerDataResult>, MinerError> {
+    let _timer = CommandTimer::start("get_miner_data_batch", || {
+        format!("ips={:?} temp_unit={:?} normalize_units={:?}", ips, temp_unit, normalize_units)
+    });
+    let mut results = fetch_many_miners(&app, ips).await?;
+    let unit = temp_unit.unwrap_or_default();
+    let normalize_units = normalize_units.unwrap_or(false);
+    for data in results.iter_mut().filter_map(|r| r.data.as_mut()) {
+        if normalize_units {
+            apply_unit_normalization(data);
+        }
+        apply_temp_unit(data, unit);
+    }
+    Ok(results)
+}
+
+// Command to summarise a swarm: totals, average temp and efficiency
+// Unreachable miners are counted but otherwise ignored
+#[tauri::command]
+async fn get_swarm_summary(app: AppHandle, ips: Vec<String>) -> Result<SwarmSummary, MinerError> {
+    let _timer = CommandTimer::start("get_swarm_summary", || format!("ips={:?}", ips));
+    let results = fetch_many_miners(&app, ips).await?;
+
+    let mut summary = SwarmSummary {
+        miner_count: results.len(),
+        ..Default::default()
+    };
+    let mut temps = Vec::new();
+
+    for data in results.iter().filter_map(|r| r.data.as_ref()) {
+        summary.reachable_count += 1;
+        summary.total_hashrate += data.get("hashRate").and_then(|v| v.as_f64()).unwrap_or(0.0);
+        summary.total_power += data.get("power").and_then(|v| v.as_f64()).unwrap_or(0.0);
+        if let Some(temp) = data.get("temp").and_then(|v| v.as_f64()) {
+            temps.push(temp);
+        }
+    }
+
+    if !temps.is_empty() {
+        summary.avg_temp = Some(temps.iter().sum::<f64>() / temps.len() as f64);
+    }
+    // hashRate is GH/s, so W / (GH/s / 1000) gives J/TH
+    if summary.total_hashrate > 0.0 {
+        summary.efficiency_j_th = Some(summary.total_power / (summary.total_hashrate / 1000.0));
+    }
+
+    Ok(summary)
+}
+
+// Command to estimate what a set of miners costs to run at their current power draw
+// price_per_kwh and currency override the app settings for this estimate only; set_app_settings saves them
+#[tauri::command]
+async fn estimate_cost(
+    app: AppHandle,
+    ips: Vec<String>,
+    price_per_kwh: Option<f64>,
+    currency: Option<String>,
+) -> Result<CostEstimate, MinerError> {
+    let _timer = CommandTimer::start("estimate_cost", || {
+        format!("ips={:?} price_per_kwh={:?} currency={:?}", ips, price_per_kwh, currency)
+    });
+    let settings = load_app_settings(&app);
+    if let Some(price) = price_per_kwh {
+        if !price.is_finite() || price < 0.0 {
+            return Err(MinerError::InvalidInput(format!("Invalid electricity price: {}", price)));
+        }
+    }
+    let price_per_kwh = price_per_kwh
+        .or(settings.price_per_kwh)
+        .ok_or_else(|| MinerError::InvalidInput("No electricity price set. Pass price_per_kwh".to_string()))?;
+    let currency = currency
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .unwrap_or(settings.currency);
+
+    // Firmware that reports milliwatts is converted first
+    let mut total_watts = 0.0;
+    let mut reporting_count = 0;
+    for mut data in fetch_many_miners(&app, ips).await?.into_iter().filter_map(|r| r.data) {
+        apply_unit_normalization(&mut data);
+        if let Some(power) = normalize_miner_json(&data).power {
+            total_watts += power;
+            reporting_count += 1;
+        }
+    }
+
+    let daily_cost = total_watts * 24.0 / 1000.0 * price_per_kwh;
+    Ok(CostEstimate {
+        total_watts,
+        daily_cost,
+        monthly_cost: daily_cost * 30.0,
+        price_per_kwh,
+        currency,
+        reporting_count,
+    })
+}
+
+// POST to each path in turn and return the first response from a route the firmware has
+// Missing routes answer 404, or 405 where only GET is registered; None means none of them exist
+async fn post_first_supported(
+    client: &reqwest::Client,
+    host: &str,
+    paths: &[&str],
+    auth: Option<&MinerAuth>,
+) -> Result<Option<reqwest::Response>, reqwest::Error> {
+    for path in paths {
+        let response = with_auth(client.post(miner_url(host, path)), auth).send().await?;
+        if matches!(
+            response.status(),
+            reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::METHOD_NOT_ALLOWED
+        ) {
+            continue;
+        }
+        return Ok(Some(response));
+    }
+    Ok(None)
+}
+
+// Read the response to an action like a restart, falling back to {"success": true} for empty bodies
+async fn action_response_json(response: reqwest::Response) -> Result<serde_json::Value, MinerError> {
+    if !response.status().is_success() {
+        return Err(MinerError::HttpStatus(response.status().as_u16()));
+    }
+    Ok(response
+        .json::<serde_json::Value>()
+        .await
+        .unwrap_or_else(|_| serde_json::json!({ "success": true })))
+}
+
+// Send a restart request to a miner
+// Without explicit auth, the credentials saved for the miner are used
+async fn send_restart(
+    app: &AppHandle,
+    client: &reqwest::Client,
+    ip: &str,
+    auth: Option<&MinerAuth>,
+) -> Result<serde_json::Value, MinerError> {
+    let url = miner_url(&connect_host(ip).await, "/api/system/restart");
+    let auth = resolve_miner_auth(app, ip, auth);
+
+    let response = with_auth(client.post(&url), auth.as_ref())
+        .send()
+        .await?;
+
+    if response.status().is_success() {
+        match response.json::<serde_json::Value>().await {
+            Ok(json) => Ok(json),
+            Err(_) => Ok(serde_json::json!({"success": true})),
+        }
+    } else {
+        Err(MinerError::HttpStatus(response.status().as_u16()))
+    }
+}
+
+// Command to restart miner
+// auth overrides the credentials saved with set_miner_auth
+#[tauri::command]
+async fn restart_miner(app: AppHandle, ip: String, auth: Option<MinerAuth>) -> Result<serde_json::Value, MinerError> {
+    let _timer = CommandTimer::start("restart_miner", || format!("ip={:?}", ip));
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let result = send_restart(&app, &client, &ip, auth.as_ref()).await;
+    record_restart_result(&app, &ip, &result);
+    result
+}
+
+// Command to restart many miners at once
+// stagger_ms delays each successive restart to avoid a simultaneous power spike
+#[tauri::command]
+async fn restart_miners(
+    app: AppHandle,
+    ips: Vec<String>,
+    stagger_ms: Option<u64>,
+) -> Result<Vec<RestartResult>, MinerError> {
+    let _timer = CommandTimer::start("restart_miners", || {
+        format!("ips={:?} stagger_ms={:?}", ips, stagger_ms)
+    });
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let stagger = stagger_ms.unwrap_or(0);
+
+    let tasks = ips.into_iter().enumerate().map(|(index, ip)| {
+        let client = &client;
+        let app = &app;
+        async move {
+            if stagger > 0 {
+                let delay = stagger.saturating_mul(index as u64);
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+            }
+            let result = send_restart(app, client, &ip, None).await;
+            record_restart_result(app, &ip, &result);
+            match result {
+                Ok(_) => RestartResult { ip, ok: true, error: None },
+                Err(e) => RestartResult { ip, ok: false, error: Some(e) },
+            }
+        }
+    });
+
+    Ok(join_all(tasks).await)
+}
+
+// Key under which a window's geometry is stored
+fn window_geometry_key(label: &str) -> String {
+    format!("window:{}", label)
+}
+
+// Save a window's current position and size to the window store
+fn save_window_geometry(window: &tauri::Window) {
+    if !GEOMETRY_WINDOWS.contains(&window.label()) || window.is_minimized().unwrap_or(false) || is_kiosk(window) {
+        return;
+    }
+    let (Ok(scale), Ok(position), Ok(size)) = (window.scale_factor(), window.outer_position(), window.inner_size()) else {
+        return;
+    };
+
+    let position = position.to_logical::<f64>(scale);
+    let size = size.to_logical::<f64>(scale);
+    let geometry = WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    };
+
+    if let (Ok(store), Ok(value)) = (window.app_handle().store(WINDOW_STORE_FILE), serde_json::to_value(&geometry)) {
+        store.set(window_geometry_key(window.label()), value);
+    }
+}
+
+// Load a window's saved geometry, ignoring it if the title bar would be off-screen
+fn load_window_geometry(app: &AppHandle, label: &str) -> Option<WindowGeometry> {
+    let store = app.store(WINDOW_STORE_FILE).ok()?;
+    let geometry: WindowGeometry = serde_json::from_value(store.get(window_geometry_key(label))?).ok()?;
+
+    let monitors = app.available_monitors().ok()?;
+    let on_screen = monitors.iter().any(|monitor| {
+        let scale = monitor.scale_factor();
+        let position = monitor.position();
+        let size = monitor.size();
+        // Check a point on the title bar so the window can still be dragged
+        let x = (geometry.x + geometry.width / 2.0) * scale;
+        let y = (geometry.y + 10.0) * scale;
+        x >= position.x as f64
+            && x < position.x as f64 + size.width as f64
+            && y >= position.y as f64
+            && y < position.y as f64 + size.height as f64
+    });
+
+    on_screen.then_some(geometry)
+}
+
+// Build a window at its saved geometry, or centered at the default size
+fn build_window_with_geometry(
+    app: &AppHandle,
+    label: &str,
+    url: WebviewUrl,
+    title: &str,
+    default_size: (f64, f64),
+    min_size: (f64, f64),
+) -> Result<(), String> {
+    let builder = WebviewWindowBuilder::new(app, label, url)
+        .title(title)
+        .min_inner_size(min_size.0, min_size.1)
+        .resizable(true);
+
+    let builder = match load_window_geometry(app, label) {
+        Some(geometry) => builder
+            .inner_size(geometry.width, geometry.height)
+            .position(geometry.x, geometry.y),
+        None => builder
+            .inner_size(default_size.0, default_size.1)
+            .center(),
+    };
+
+    builder.build().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// Build a window like build_window_with_geometry, then wait for its page to emit "window-ready"
+// A missing route otherwise leaves a blank window with no error; on timeout the window is destroyed
+async fn open_window_and_wait(
+    app: &AppHandle,
+    label: &str,
+    url: WebviewUrl,
+    title: &str,
+    default_size: (f64, f64),
+    min_size: (f64, f64),
+) -> Result<(), String> {
+    // Listen before building so a fast page can't emit before we're listening
+    let (ready_tx, ready_rx) = tokio::sync::oneshot::channel();
+    let ready_tx = Mutex::new(Some(ready_tx));
+    let expected = label.to_string();
+    let listener = app.listen(WINDOW_READY_EVENT, move |event| {
+        if serde_json::from_str::<String>(event.payload()).ok().as_deref() == Some(expected.as_str()) {
+            if let Some(tx) = ready_tx.lock().ok().and_then(|mut tx| tx.take()) {
+                let _ = tx.send(());
+            }
+        }
+    });
+
+    let built = build_window_with_geometry(app, label, url, title, default_size, min_size);
+    let ready = match built {
+        Ok(()) => tokio::time::timeout(WINDOW_READY_TIMEOUT, ready_rx).await.is_ok_and(|r| r.is_ok()),
+        Err(_) => false,
+    };
+    app.unlisten(listener);
+    built?;
+
+    if !ready {
+        if let Some(window) = app.get_webview_window(label) {
+            let _ = window.destroy();
+        }
+        return Err(format!(
+            "The {} window did not finish loading within {}s. Its page may be missing from this build",
+            label,
+            WINDOW_READY_TIMEOUT.as_secs()
+        ));
+    }
+    Ok(())
+}
+
+// Command to open analytics window
+#[tauri::command]
+async fn open_analytics_window(app: AppHandle) -> Result<(), String> {
+    let _timer = CommandTimer::start("open_analytics_window", String::new);
+    // Check if window already exists
+    if let Some(window) = app.get_webview_window("analytics") {
+        // Window exists, focus it
+        window.set_focus().map_err(|e| e.to_string())?;
+        return Ok(());
+    }
+
+    // Create new analytics window
+    let url = WebviewUrl::App("analytics".into());
+
+    open_window_and_wait(&app, "analytics", url, "Mining Analytics - AxeOS Live!", (1200.0, 800.0), (800.0, 600.0)).await?;
+
+    Ok(())
+}
+
+// Command to close analytics window
+#[tauri::command]
+async fn close_analytics_window(app: AppHandle) -> Result<(), String> {
+    let _timer = CommandTimer::start("close_analytics_window", String::new);
+    if let Some(window) = app.get_webview_window("analytics") {
+        window.close().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+// Command to open settings window
+#[tauri::command]
+async fn open_settings_window(app: AppHandle) -> Result<(), String> {
+    let _timer = CommandTimer::start("open_settings_window", String::new);
+    // Check if window already exists
+    if let Some(window) = app.get_webview_window("settings") {
+        // Window exists, focus it
+        window.set_focus().map_err(|e| e.to_string())?;
+        return Ok(());
+    }
+
+    // Create new settings window
+    let url = WebviewUrl::App("settings".into());
+
+    open_window_and_wait(&app, "settings", url, "Settings - AxeOS Live!", (500.0, 700.0), (400.0, 500.0)).await?;
+
+    Ok(())
+}
+
+// Command to close settings window
+#[tauri::command]
+async fn close_settings_window(app: AppHandle) -> Result<(), String> {
+    let _timer = CommandTimer::start("close_settings_window", String::new);
+    if let Some(window) = app.get_webview_window("settings") {
+        window.close().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+// Command to open tools window
+#[tauri::command]
+async fn open_tools_window(app: AppHandle) -> Result<(), String> {
+    let _timer = CommandTimer::start("open_tools_window", String::new);
+    // Check if window already exists
+    if let Some(window) = app.get_webview_window("tools") {
+        // Window exists, focus it
+        window.set_focus().map_err(|e| e.to_string())?;
+        return Ok(());
+    }
+
+    // Create new tools window
+    let url = WebviewUrl::App("tools".into());
+
+    open_window_and_wait(&app, "tools", url, "Tools & Settings - AxeOS Live!", (500.0, 750.0), (450.0, 600.0)).await?;
+
+    Ok(())
+}
+
+// Command to open benchmark window with larger size
+// Optional miner_ip / miner_ips pre-select miners; the first is always sent as "miner" /
+// "select-miner" so single-miner pages keep working, and several also go out as a
+// comma-separated "miners" parameter / "select-miners" event for side-by-side benchmarks
+#[tauri::command]
+async fn open_benchmark_window(
+    app: AppHandle,
+    miner_ip: Option<String>,
+    miner_ips: Option<Vec<String>>,
+) -> Result<(), String> {
+    let _timer = CommandTimer::start("open_benchmark_window", || {
+        format!("miner_ip={:?} miner_ips={:?}", miner_ip, miner_ips)
+    });
+    let mut miners: Vec<String> = Vec::new();
+    for ip in miner_ip.into_iter().chain(miner_ips.into_iter().flatten()) {
+        let ip = ip.trim().to_string();
+        if !ip.is_empty() && !miners.contains(&ip) {
+            miners.push(ip);
+        }
+    }
+
+    // Check if window already exists
+    if let Some(window) = app.get_webview_window("benchmark") {
+        // Window exists, focus it and emit events to select the miners
+        window.set_focus().map_err(|e| e.to_string())?;
+        if let Some(ip) = miners.first() {
+            window.emit("select-miner", ip).map_err(|e| e.to_string())?;
+        }
+        if miners.len() > 1 {
+            window.emit("select-miners", &miners).map_err(|e| e.to_string())?;
+        }
+        return Ok(());
+    }
+
+    // Create new benchmark window with larger dimensions
+    // Append the miners as query parameters if provided
+    let url = match miners.as_slice() {
+        [] => WebviewUrl::App("benchmark".into()),
+        [ip] => WebviewUrl::App(format!("benchmark?miner={}", ip).into()),
+        [first, ..] => WebviewUrl::App(format!("benchmark?miner={}&miners={}", first, miners.join(",")).into()),
+    };
+
+    open_window_and_wait(&app, "benchmark", url, "Hashrate Benchmark - AxeOS Live!", (900.0, 800.0), (700.0, 600.0)).await?;
+
+    Ok(())
+}
+
+// Command to close the open secondary windows and reopen them centered at their default size
+// Their saved geometry is dropped; returns the labels of the windows that were recreated
+// Every window is reopened even if one fails, and the failures are reported together
+#[tauri::command]
+async fn relayout_windows(app: AppHandle) -> Result<Vec<String>, String> {
+    let _timer = CommandTimer::start("relayout_windows", String::new);
+    let store = app.store(WINDOW_STORE_FILE).map_err(|e| e.to_string())?;
+    let mut closed = Vec::new();
+    let mut errors = Vec::new();
+
+    for label in SECONDARY_WINDOWS {
+        let Some(window) = app.get_webview_window(label) else {
+            continue;
+        };
+        if let Err(e) = window.destroy() {
+            errors.push(format!("{}: {}", label, e));
+            continue;
+        }
+
+        // The label can't be reused until the old window is gone
+        let deadline = Instant::now() + WINDOW_CLOSE_TIMEOUT;
+        while app.get_webview_window(label).is_some() && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+
+        store.delete(window_geometry_key(label));
+        if let Ok(mut windows) = app.state::<KioskState>().windows.lock() {
+            windows.remove(*label);
+        }
+        closed.push(label.to_string());
+    }
+    if let Err(e) = store.save() {
+        errors.push(format!("{}: {}", WINDOW_STORE_FILE, e));
+    }
+
+    let mut reopened = Vec::new();
+    for label in closed {
+        let result = match label.as_str() {
+            "analytics" => open_analytics_window(app.clone()).await,
+            "settings" => open_settings_window(app.clone()).await,
+            "tools" => open_tools_window(app.clone()).await,
+            "benchmark" => open_benchmark_window(app.clone(), None, None).await,
+            _ => Ok(()),
+        };
+        match result {
+            Ok(()) => reopened.push(label),
+            Err(e) => errors.push(format!("{}: {}", label, e)),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(format!("Failed to relayout windows: {}", errors.join("; ")));
+    }
+    Ok(reopened)
+}
+
+// Send a PATCH with a JSON body to the miner's /api/system endpoint
+async fn send_system_patch<T: Serialize + ?Sized>(
+    ip: &str,
+    body: &T,
+    auth: Option<&MinerAuth>,
+) -> Result<reqwest::Response, MinerError> {
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    send_system_patch_to(&client, &connect_host(ip).await, body, auth).await
+}
+
+// PATCH /api/system on an already resolved host, for callers that send several patches in a row
+async fn send_system_patch_to<T: Serialize + ?Sized>(
+    client: &reqwest::Client,
+    host: &str,
+    body: &T,
+    auth: Option<&MinerAuth>,
+) -> Result<reqwest::Response, MinerError> {
+    let url = miner_url(host, "/api/system");
+
+    // Some boards reject anything but a plain application/json body with a fixed length,
+    // so serialize up front instead of leaving the framing to the client
+    let payload = serde_json::to_vec(body).map_err(|e| MinerError::Internal(e.to_string()))?;
+
+    with_auth(client.patch(&url), auth)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(reqwest::header::CONTENT_LENGTH, payload.len())
+        .body(payload)
+        .send()
+        .await
+        .map_err(MinerError::from)
+}
+
+// Older firmware answers a PATCH with a field it doesn't know with one of these statuses
+// 401/403 are left out so an auth failure isn't reported as missing firmware support
+fn rejects_unknown_field(status: reqwest::StatusCode) -> bool {
+    matches!(
+        status,
+        reqwest::StatusCode::BAD_REQUEST
+            | reqwest::StatusCode::NOT_FOUND
+            | reqwest::StatusCode::METHOD_NOT_ALLOWED
+            | reqwest::StatusCode::UNPROCESSABLE_ENTITY
+    )
+}
+
+// Read a settings response, falling back to {"success": true} for empty bodies
+// A rejection returns the miner's status and body along with what was sent
+async fn settings_response_json<T: Serialize + ?Sized>(
+    response: reqwest::Response,
+    sent: &T,
+) -> Result<serde_json::Value, MinerError> {
+    if response.status().is_success() {
+        match response.json::<serde_json::Value>().await {
+            Ok(json) => Ok(json),
+            Err(_) => Ok(serde_json::json!({"success": true})),
+        }
+    } else {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        log::warn!("Failed to update settings ({}): {}", status, error_text);
+        Err(MinerError::Rejected {
+            status: status.as_u16(),
+            body: error_text,
+            sent: serde_json::to_value(sent).unwrap_or_default(),
+        })
+    }
+}
+
+// PATCH a JSON body to the miner's /api/system endpoint
+// Without explicit auth, the credentials saved for the miner are used
+async fn patch_miner_system<T: Serialize + ?Sized>(
+    app: &AppHandle,
+    ip: &str,
+    body: &T,
+    auth: Option<&MinerAuth>,
+) -> Result<serde_json::Value, MinerError> {
+    let auth = resolve_miner_auth(app, ip, auth);
+    let response = send_system_patch(ip, body, auth.as_ref()).await?;
+    settings_response_json(response, body).await
+}
+
+// Look up the safe operating range for an ASIC model string (e.g. "BM1370")
+fn safe_range_for_model(model: &str) -> Option<&'static AsicSafeRange> {
+    let model = model.to_uppercase();
+    ASIC_SAFE_RANGES.iter().find(|range| model.contains(range.model))
+}
+
+// Suggested core voltage for a frequency on the given ASIC, kept within its safe voltage range
+fn voltage_suggestion(model: &str, frequency: u32) -> Result<VoltageSuggestion, MinerError> {
+    let upper = model.to_uppercase();
+    let (Some(curve), Some(range)) = (
+        ASIC_VOLTAGE_CURVES.iter().find(|curve| upper.contains(curve.model)),
+        safe_range_for_model(model),
+    ) else {
+        return Err(MinerError::Unsupported(format!("No voltage curve known for ASIC model '{}'", model)));
+    };
+    if !(range.min_frequency..=range.max_frequency).contains(&frequency) {
+        return Err(MinerError::InvalidInput(format!(
+            "Frequency {}MHz is outside the safe range for {} ({}-{}MHz)",
+            frequency, range.model, range.min_frequency, range.max_frequency
+        )));
+    }
+
+    let points = curve.points;
+    let (first, last) = (points[0], points[points.len() - 1]);
+    let suggested = if frequency <= first.0 {
+        first.1
+    } else if frequency >= last.0 {
+        last.1
+    } else {
+        let upper_index = points.iter().position(|&(f, _)| f >= frequency).unwrap_or(points.len() - 1);
+        let ((f0, v0), (f1, v1)) = (points[upper_index - 1], points[upper_index]);
+        let t = f64::from(frequency - f0) / f64::from(f1 - f0);
+        (f64::from(v0) + t * (f64::from(v1) - f64::from(v0))).round() as u32
+    };
+    let suggested = suggested.clamp(range.min_voltage, range.max_voltage);
+
+    Ok(VoltageSuggestion {
+        suggested_mv: suggested,
+        min_mv: suggested.saturating_sub(VOLTAGE_SUGGESTION_MARGIN_MV).max(range.min_voltage),
+        max_mv: (suggested + VOLTAGE_SUGGESTION_MARGIN_MV).min(range.max_voltage),
+    })
+}
+
+// Command to suggest a core voltage for a target frequency on an ASIC model (no network access)
+#[tauri::command]
+async fn suggest_voltage(model: String, frequency: u32) -> Result<VoltageSuggestion, MinerError> {
+    let _timer = CommandTimer::start("suggest_voltage", || {
+        format!("model={:?} frequency={:?}", model, frequency)
+    });
+    voltage_suggestion(&model, frequency)
+}
+
+// Check frequency/voltage against the model's safe range
+fn validate_settings_for_model(model: &str, frequency: u32, core_voltage: u32) -> Result<(), MinerError> {
+    let Some(range) = safe_range_for_model(model) else {
+        log::warn!("No safe range known for ASIC model {}, skipping validation", model);
+        return Ok(());
+    };
+
+    if !(range.min_frequency..=range.max_frequency).contains(&frequency) {
+        return Err(MinerError::InvalidInput(format!(
+            "Frequency {}MHz is outside the safe range for {} ({}-{}MHz). Pass force to override",
+            frequency, range.model, range.min_frequency, range.max_frequency
+        )));
+    }
+    if !(range.min_voltage..=range.max_voltage).contains(&core_voltage) {
+        return Err(MinerError::InvalidInput(format!(
+            "Core voltage {}mV is outside the safe range for {} ({}-{}mV). Pass force to override",
+            core_voltage, range.model, range.min_voltage, range.max_voltage
+        )));
+    }
+    // Safe but likely unstable: allowed, since some chips do run lean
+    if let Ok(suggestion) = voltage_suggestion(model, frequency) {
+        if core_voltage < suggestion.min_mv {
+            log::warn!(
+                "Core voltage {}mV is below the suggested {}-{}mV for {}MHz on {}",
+                core_voltage, suggestion.min_mv, suggestion.max_mv, frequency, range.model
+            );
+        }
+    }
+    Ok(())
+}
+
+// Command to update miner settings
+// Values are checked against the ASIC's safe range unless force is set;
+// the model is read from the miner when not provided.
+// auth overrides the credentials saved with set_miner_auth.
+// dry_run validates and returns the request that would be sent without sending it
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn update_miner_settings(
+    app: AppHandle,
+    ip: String,
+    frequency: u32,
+    core_voltage: u32,
+    model: Option<String>,
+    force: Option<bool>,
+    auth: Option<MinerAuth>,
+    dry_run: Option<bool>,
+) -> Result<serde_json::Value, MinerError> {
+    let _timer = CommandTimer::start("update_miner_settings", || {
+        format!(
+            "ip={:?} frequency={:?} core_voltage={:?} model={:?} force={:?} dry_run={:?}",
+            ip, frequency, core_voltage, model, force, dry_run
+        )
+    });
+
+    if !force.unwrap_or(false) {
+        let model = match model {
+            Some(model) => model,
+            None => {
+                let client = http_client_builder()
+                    .timeout(Duration::from_secs(10))
+                    .build()?;
+                let info = fetch_miner_data(&app, &client, &ip, 0, auth.as_ref()).await?;
+                info.get("ASICModel")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string())
+                    .ok_or_else(|| MinerError::Unsupported(format!("Could not determine ASIC model for {}. Pass force to skip validation", ip)))?
+            }
+        };
+        validate_settings_for_model(&model, frequency, core_voltage)?;
+    }
+
+    let settings = MinerSettingsUpdate {
+        frequency,
+        core_voltage,
+    };
+
+    if dry_run.unwrap_or(false) {
+        return Ok(serde_json::json!({
+            "dry_run": true,
+            "method": "PATCH",
+            "url": miner_url(&ip, "/api/system"),
+            "body": settings,
+        }));
+    }
+
+    let result = patch_miner_system(&app, &ip, &settings, auth.as_ref()).await?;
+    log_event(&app, &ip, MinerEventKind::SettingsChanged, format!("frequency={}MHz coreVoltage={}mV", frequency, core_voltage));
+    Ok(result)
+}
+
+// Command to apply a built-in overclock preset by name (e.g. "485_1250")
+// Goes through update_miner_settings, so the preset must fit the detected ASIC's safe range
+#[tauri::command]
+async fn apply_overclock_preset(app: AppHandle, ip: String, preset: String) -> Result<serde_json::Value, MinerError> {
+    let _timer = CommandTimer::start("apply_overclock_preset", || format!("ip={:?} preset={:?}", ip, preset));
+    let preset = OVERCLOCK_PRESETS
+        .iter()
+        .find(|p| p.name == preset.trim())
+        .ok_or_else(|| {
+            let known: Vec<&str> = OVERCLOCK_PRESETS.iter().map(|p| p.name).collect();
+            MinerError::InvalidInput(format!("Unknown overclock preset: {}. Known presets: {}", preset, known.join(", ")))
+        })?;
+
+    update_miner_settings(app, ip, preset.frequency, preset.core_voltage, None, None, None, None).await?;
+
+    Ok(serde_json::json!({
+        "preset": preset.name,
+        "frequency": preset.frequency,
+        "coreVoltage": preset.core_voltage,
+    }))
+}
+
+// Check a PATCH body only holds PATCHABLE_SYSTEM_FIELDS and, unless force is set, that its
+// frequency/coreVoltage are within the ASIC's safe range
+// (when only one is given, the miner's current value is used for the other)
+async fn validate_system_patch(
+    app: &AppHandle,
+    ip: &str,
+    fields: &serde_json::Map<String, serde_json::Value>,
+    force: bool,
+) -> Result<(), MinerError> {
+    if fields.is_empty() {
+        return Err(MinerError::InvalidInput("No fields to update".to_string()));
+    }
+    let unknown: Vec<&str> = fields
+        .keys()
+        .map(String::as_str)
+        .filter(|key| !PATCHABLE_SYSTEM_FIELDS.contains(key))
+        .collect();
+    if !unknown.is_empty() {
+        return Err(MinerError::InvalidInput(format!("Fields not allowed: {}", unknown.join(", "))));
+    }
+
+    let integer_field = |key: &str| -> Result<Option<u32>, MinerError> {
+        match fields.get(key) {
+            None => Ok(None),
+            Some(value) => value
+                .as_u64()
+                .and_then(|n| u32::try_from(n).ok())
+                .map(Some)
+                .ok_or_else(|| MinerError::InvalidInput(format!("{} must be a positive integer", key))),
+        }
+    };
+    let frequency = integer_field("frequency")?;
+    let core_voltage = integer_field("coreVoltage")?;
+
+    if (frequency.is_some() || core_voltage.is_some()) && !force {
+        let client = http_client_builder()
+            .timeout(Duration::from_secs(10))
+            .build()?;
+        let info = normalize_miner_json(&fetch_miner_data(app, &client, ip, 0, None).await?);
+        let unknown_setting = || {
+            MinerError::Unsupported(format!("Could not read the current settings of {}. Pass force to skip validation", ip))
+        };
+        let model = info.asic_model.ok_or_else(unknown_setting)?;
+        let frequency = frequency.or(info.frequency.map(|f| f.round() as u32)).ok_or_else(unknown_setting)?;
+        let core_voltage = core_voltage.or(info.core_voltage.map(|v| v.round() as u32)).ok_or_else(unknown_setting)?;
+        validate_settings_for_model(&model, frequency, core_voltage)?;
+    }
+    Ok(())
+}
+
+// Command to PATCH any set of the fields in PATCHABLE_SYSTEM_FIELDS, for settings without a typed command
+// frequency/coreVoltage are checked against the ASIC's safe range unless force is set (see validate_system_patch)
+#[tauri::command]
+async fn patch_system(
+    app: AppHandle,
+    ip: String,
+    fields: serde_json::Map<String, serde_json::Value>,
+    force: Option<bool>,
+) -> Result<serde_json::Value, MinerError> {
+    let _timer = CommandTimer::start("patch_system", || format!("ip={:?} force={:?}", ip, force));
+    validate_system_patch(&app, &ip, &fields, force.unwrap_or(false)).await?;
+
+    let result = patch_miner_system(&app, &ip, &fields, None).await?;
+    let keys: Vec<&str> = fields.keys().map(String::as_str).collect();
+    log_event(&app, &ip, MinerEventKind::SettingsChanged, format!("patched {}", keys.join(", ")));
+    Ok(result)
+}
+
+// Command to apply settings and confirm the miner actually took them
+// AxeOS may accept a PATCH but clamp the values or defer them until restart, so the miner
+// is re-read after a short settle and its configured frequency/coreVoltage compared to the request
+#[tauri::command]
+async fn update_and_verify(
+    app: AppHandle,
+    ip: String,
+    frequency: u32,
+    core_voltage: u32,
+) -> Result<SettingsVerification, MinerError> {
+    let _timer = CommandTimer::start("update_and_verify", || {
+        format!("ip={:?} frequency={:?} core_voltage={:?}", ip, frequency, core_voltage)
+    });
+    update_miner_settings(app.clone(), ip.clone(), frequency, core_voltage, None, None, None, None).await?;
+    tokio::time::sleep(Duration::from_secs(VERIFY_SETTLE_SECS)).await;
+
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+    let info = normalize_miner_json(&fetch_miner_data(&app, &client, &ip, DEFAULT_FETCH_RETRIES, None).await?);
+
+    // Firmware reports whole MHz/mV, so allow for float rounding only
+    let matches = |actual: Option<f64>, requested: u32| actual.is_some_and(|v| (v - f64::from(requested)).abs() < 0.5);
+    Ok(SettingsVerification {
+        applied: matches(info.frequency, frequency) && matches(info.core_voltage, core_voltage),
+        actual_frequency: info.frequency,
+        actual_voltage: info.core_voltage,
+    })
+}
+
+// Command to apply settings to several miners at once
+// Each target goes through update_miner_settings (validation, saved auth, dry_run) concurrently
+#[tauri::command]
+async fn update_miner_settings_batch(
+    app: AppHandle,
+    targets: Vec<SettingsTarget>,
+    force: Option<bool>,
+    dry_run: Option<bool>,
+) -> Result<Vec<SettingsResult>, MinerError> {
+    let _timer = CommandTimer::start("update_miner_settings_batch", || {
+        format!("targets={} force={:?} dry_run={:?}", targets.len(), force, dry_run)
+    });
+    let tasks = targets.into_iter().map(|target| {
+        let app = app.clone();
+        async move {
+            let result = update_miner_settings(
+                app,
+                target.ip.clone(),
+                target.frequency,
+                target.core_voltage,
+                None,
+                force,
+                None,
+                dry_run,
+            )
+            .await;
+            match result {
+                Ok(data) => SettingsResult { ip: target.ip, ok: true, data: Some(data), error: None },
+                Err(e) => SettingsResult { ip: target.ip, ok: false, data: None, error: Some(e) },
+            }
+        }
+    });
+
+    Ok(join_all(tasks).await)
+}
+
+// Command to put a misconfigured miner back on safe defaults and restart it
+// AxeOS has no factory-reset API, so this applies the stock frequency/voltage for the
+// detected ASIC plus automatic fan control, then restarts
+#[tauri::command]
+async fn reset_miner_settings(app: AppHandle, ip: String) -> Result<serde_json::Value, MinerError> {
+    let _timer = CommandTimer::start("reset_miner_settings", || format!("ip={:?}", ip));
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let info = normalize_miner_json(&fetch_miner_data(&app, &client, &ip, DEFAULT_FETCH_RETRIES, None).await?);
+    let model = info.asic_model.unwrap_or_default();
+    let range = safe_range_for_model(&model).ok_or_else(|| {
+        MinerError::Unsupported(format!(
+            "AxeOS has no factory-reset API and no safe defaults are known for ASIC model '{}'. Reset it from the miner's web UI",
+            model
+        ))
+    })?;
+
+    let defaults = serde_json::json!({
+        "frequency": range.stock_frequency,
+        "coreVoltage": range.stock_voltage,
+        "autofanspeed": 1,
+    });
+    patch_miner_system(&app, &ip, &defaults, None).await?;
+    log_event(&app, &ip, MinerEventKind::SettingsChanged, format!("reset to {} stock defaults", range.model));
+
+    let restart = send_restart(&app, &client, &ip, None).await;
+    record_restart_result(&app, &ip, &restart);
+    restart?;
+
+    Ok(serde_json::json!({
+        "success": true,
+        "note": "AxeOS has no factory-reset API; applied stock defaults for the detected ASIC and restarted",
+        "model": range.model,
+        "frequency": range.stock_frequency,
+        "coreVoltage": range.stock_voltage,
+    }))
+}
+
+// Command to update the miner's primary (and optionally fallback) pool
+#[tauri::command]
+async fn update_pool_settings(
+    app: AppHandle,
+    ip: String,
+    primary: PoolConfig,
+    fallback: Option<PoolConfig>,
+) -> Result<serde_json::Value, MinerError> {
+    let _timer = CommandTimer::start("update_pool_settings", || format!("ip={:?}", ip));
+    primary.validate()?;
+    if let Some(fallback) = &fallback {
+        fallback.validate()?;
+    }
+
+    let mut body = serde_json::Map::new();
+    body.insert("stratumURL".to_string(), primary.url.into());
+    body.insert("stratumPort".to_string(), primary.port.into());
+    body.insert("stratumUser".to_string(), primary.user.into());
+    body.insert("stratumPassword".to_string(), primary.password.into());
+
+    if let Some(fallback) = fallback {
+        body.insert("fallbackStratumURL".to_string(), fallback.url.into());
+        body.insert("fallbackStratumPort".to_string(), fallback.port.into());
+        body.insert("fallbackStratumUser".to_string(), fallback.user.into());
+        body.insert("fallbackStratumPassword".to_string(), fallback.password.into());
+    }
+
+    let result = patch_miner_system(&app, &ip, &body, None).await?;
+    log_event(&app, &ip, MinerEventKind::SettingsChanged, "pool configuration updated");
+    Ok(result)
+}
+
+// Command to apply the same pool configuration to several miners at once
+// The config is validated once up front; each miner then goes through update_pool_settings concurrently
+#[tauri::command]
+async fn update_pool_settings_batch(
+    app: AppHandle,
+    ips: Vec<String>,
+    primary: PoolConfig,
+    fallback: Option<PoolConfig>,
+) -> Result<Vec<SettingsResult>, MinerError> {
+    let _timer = CommandTimer::start("update_pool_settings_batch", || format!("ips={:?}", ips));
+    primary.validate()?;
+    if let Some(fallback) = &fallback {
+        fallback.validate()?;
+    }
+
+    let tasks = ips.into_iter().map(|ip| {
+        let app = app.clone();
+        let primary = primary.clone();
+        let fallback = fallback.clone();
+        async move {
+            match update_pool_settings(app, ip.clone(), primary, fallback).await {
+                Ok(data) => SettingsResult { ip, ok: true, data: Some(data), error: None },
+                Err(e) => SettingsResult { ip, ok: false, data: None, error: Some(e) },
+            }
+        }
+    });
+
+    Ok(join_all(tasks).await)
+}
+
+// Command to back up the settings of several miners to a single JSON file
+// Unreachable miners are left out of the file and reported in failed
+#[tauri::command]
+async fn export_swarm_config(app: AppHandle, ips: Vec<String>, path: String) -> Result<SwarmExportSummary, MinerError> {
+    let _timer = CommandTimer::start("export_swarm_config", || format!("ips={:?} path={:?}", ips, path));
+    let results = fetch_many_miners(&app, ips).await?;
+
+    let mut miners = Vec::new();
+    let mut failed = Vec::new();
+    for result in results {
+        match &result.data {
+            Some(data) => miners.push(MinerConfigSnapshot::from_miner(result.ip.clone(), data)),
+            None => failed.push(result),
+        }
+    }
+
+    let config = SwarmConfig {
+        exported_at: unix_now(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        miners,
+    };
+    let contents = serde_json::to_string_pretty(&config).map_err(|e| MinerError::Internal(e.to_string()))?;
+    tokio::fs::write(&path, contents)
+        .await
+        .map_err(|e| MinerError::InvalidInput(format!("Failed to write {}: {}", path, e)))?;
+
+    Ok(SwarmExportSummary {
+        path,
+        exported: config.miners.len(),
+        failed,
+    })
+}
+
+// Command to read a swarm config file, optionally reapplying each miner's settings to its IP
+// Pool passwords aren't exported, so miners keep their current ones; frequency/coreVoltage are
+// checked against each miner's safe range like patch_system, since the file may be hand-edited
+#[tauri::command]
+async fn import_swarm_config(app: AppHandle, path: String, apply: bool) -> Result<SwarmImportSummary, MinerError> {
+    let _timer = CommandTimer::start("import_swarm_config", || format!("path={:?} apply={:?}", path, apply));
+    let contents = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| MinerError::InvalidInput(format!("Failed to read {}: {}", path, e)))?;
+    let config: SwarmConfig = serde_json::from_str(&contents)
+        .map_err(|e| MinerError::InvalidInput(format!("Invalid swarm config file {}: {}", path, e)))?;
+
+    let results = if apply {
+        let tasks = config.miners.iter().map(|miner| {
+            let app = app.clone();
+            async move {
+                let body = miner.patch_body();
+                let result = match validate_system_patch(&app, &miner.ip, &body, false).await {
+                    Ok(()) => patch_miner_system(&app, &miner.ip, &body, None).await,
+                    Err(e) => Err(e),
+                };
+                if result.is_ok() {
+                    log_event(&app, &miner.ip, MinerEventKind::SettingsChanged, "settings restored from swarm config");
+                }
+                match result {
+                    Ok(data) => SettingsResult { ip: miner.ip.clone(), ok: true, data: Some(data), error: None },
+                    Err(e) => SettingsResult { ip: miner.ip.clone(), ok: false, data: None, error: Some(e) },
+                }
+            }
+        });
+        join_all(tasks).await
+    } else {
+        Vec::new()
+    };
+
+    Ok(SwarmImportSummary {
+        exported_at: config.exported_at,
+        miners: config.miners,
+        results,
+    })
+}
+
+// Host part of a pool URL as AxeOS stores it ("stratum+tcp://pool.example.com:3333/x" -> "pool.example.com")
+fn pool_host(url: &str) -> &str {
+    let url = url.trim();
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = without_scheme.split('/').next().unwrap_or_default();
+    // Bracketed IPv6 keeps its colons; otherwise drop any ":port"
+    match authority.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or_default(),
+        None => authority.split(':').next().unwrap_or_default(),
+    }
+}
+
+// Command to check a pool endpoint resolves and accepts TCP connections before applying it
+#[tauri::command]
+async fn test_pool(url: String, port: u16) -> Result<PoolTestResult, MinerError> {
+    let _timer = CommandTimer::start("test_pool", || format!("url={:?} port={:?}", url, port));
+    let host = pool_host(&url);
+    if host.is_empty() || port == 0 {
+        return Err(MinerError::InvalidInput(format!("Invalid pool address: {}:{}", url, port)));
+    }
+
+    let addrs: Vec<std::net::SocketAddr> = match tokio::net::lookup_host((host, port)).await {
+        Ok(addrs) => addrs.collect(),
+        Err(_) => Vec::new(),
+    };
+    let Some(addr) = addrs.first() else {
+        return Ok(PoolTestResult {
+            resolvable: false,
+            tcp_open: false,
+            latency_ms: None,
+        });
+    };
+
+    let started = Instant::now();
+    let connect = tokio::net::TcpStream::connect(addr);
+    let tcp_open = matches!(
+        tokio::time::timeout(Duration::from_millis(POOL_TEST_TIMEOUT_MS), connect).await,
+        Ok(Ok(_))
+    );
+
+    Ok(PoolTestResult {
+        resolvable: true,
+        tcp_open,
+        latency_ms: tcp_open.then(|| started.elapsed().as_millis() as u64),
+    })
+}
+
+// Check a hostname against DNS label rules
+fn validate_hostname(hostname: &str) -> Result<(), MinerError> {
+    if hostname.is_empty() || hostname.len() > 63 {
+        return Err(MinerError::InvalidInput(format!("Invalid hostname: {}. Must be 1-63 characters", hostname)));
+    }
+    if !hostname.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(MinerError::InvalidInput(format!("Invalid hostname: {}. Only letters, digits and hyphens are allowed", hostname)));
+    }
+    if hostname.starts_with('-') || hostname.ends_with('-') {
+        return Err(MinerError::InvalidInput(format!("Invalid hostname: {}. Cannot start or end with a hyphen", hostname)));
+    }
+    Ok(())
+}
+
+// Command to rename a miner, optionally restarting it so the new name takes effect
+#[tauri::command]
+async fn set_hostname(app: AppHandle, ip: String, hostname: String, restart: Option<bool>) -> Result<serde_json::Value, MinerError> {
+    let _timer = CommandTimer::start("set_hostname", || {
+        format!("ip={:?} hostname={:?} restart={:?}", ip, hostname, restart)
+    });
+    validate_hostname(&hostname)?;
+
+    let body = serde_json::json!({ "hostname": hostname });
+    let result = patch_miner_system(&app, &ip, &body, None).await?;
+
+    // Through restart_miner so the restart is logged like any other
+    if restart.unwrap_or(false) {
+        restart_miner(app, ip, None).await?;
+    }
+
+    Ok(result)
+}
+
+// Command to set fan control: automatic, or a fixed manual percentage
+#[tauri::command]
+async fn set_fan_control(app: AppHandle, ip: String, auto: bool, manual_percent: Option<u8>) -> Result<serde_json::Value, MinerError> {
+    let _timer = CommandTimer::start("set_fan_control", || {
+        format!("ip={:?} auto={:?} manual_percent={:?}", ip, auto, manual_percent)
+    });
+    let body = if auto {
+        serde_json::json!({ "autofanspeed": 1 })
+    } else {
+        let percent = manual_percent
+            .ok_or_else(|| MinerError::InvalidInput("manual_percent is required when auto fan control is off".to_string()))?
+            .min(100);
+        serde_json::json!({ "autofanspeed": 0, "fanspeed": percent })
+    };
+
+    patch_miner_system(&app, &ip, &body, None).await
+}
+
+// Command to save a named tuning profile, replacing any with the same name
+#[tauri::command]
+async fn save_profile(
+    app: AppHandle,
+    name: String,
+    frequency: u32,
     core_voltage: u32,
+    fan: Option<u8>,
+) -> Result<(), String> {
+    let _timer = CommandTimer::start("save_profile", || {
+        format!(
+            "name={:?} frequency={:?} core_voltage={:?} fan={:?}",
+            name, frequency, core_voltage, fan
+        )
+    });
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Profile name cannot be empty".to_string());
+    }
+
+    let profile = TuningProfile {
+        frequency,
+        core_voltage,
+        fan: fan.map(|percent| percent.min(100)),
+    };
+    let store = app.store(TUNING_PROFILES_STORE_FILE).map_err(|e| e.to_string())?;
+    store.set(name, serde_json::to_value(&profile).map_err(|e| e.to_string())?);
+    store.save().map_err(|e| e.to_string())
+}
+
+// Command to list saved tuning profile names
+#[tauri::command]
+async fn list_profiles(app: AppHandle) -> Result<Vec<String>, String> {
+    let _timer = CommandTimer::start("list_profiles", String::new);
+    let store = app.store(TUNING_PROFILES_STORE_FILE).map_err(|e| e.to_string())?;
+    let mut names = store.keys();
+    names.sort();
+    Ok(names)
+}
+
+// Command to apply a saved tuning profile to several miners
+// Frequency/voltage go through update_miner_settings_batch; the fan is set on miners that accepted them
+#[tauri::command]
+async fn apply_profile(
+    app: AppHandle,
+    name: String,
+    ips: Vec<String>,
+    force: Option<bool>,
+) -> Result<Vec<SettingsResult>, MinerError> {
+    let _timer = CommandTimer::start("apply_profile", || {
+        format!("name={:?} ips={:?} force={:?}", name, ips, force)
+    });
+    let profile: TuningProfile = app
+        .store(TUNING_PROFILES_STORE_FILE)
+        .map_err(|e| MinerError::Internal(e.to_string()))?
+        .get(&name)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .ok_or_else(|| MinerError::InvalidInput(format!("Unknown profile: {}", name)))?;
+
+    let targets = ips
+        .into_iter()
+        .map(|ip| SettingsTarget {
+            ip,
+            frequency: profile.frequency,
+            core_voltage: profile.core_voltage,
+        })
+        .collect();
+    let mut results = update_miner_settings_batch(app.clone(), targets, force, None).await?;
+
+    let fan = profile.fan;
+    let app = &app;
+    let fan_updates = results.iter_mut().filter(|result| result.ok).map(|result| async move {
+        if let Err(e) = set_fan_control(app.clone(), result.ip.clone(), fan.is_none(), fan).await {
+            result.ok = false;
+            result.error = Some(e);
+        }
+    });
+    join_all(fan_updates).await;
+
+    Ok(results)
+}
+
+// Command to turn the miner's display on or off
+// Uses displayTimeout: -1 keeps the screen always on, 0 turns it off
+#[tauri::command]
+async fn set_display(app: AppHandle, ip: String, on: bool) -> Result<serde_json::Value, MinerError> {
+    let _timer = CommandTimer::start("set_display", || format!("ip={:?} on={:?}", ip, on));
+    let timeout = if on { -1 } else { 0 };
+    let body = serde_json::json!({ "displayTimeout": timeout });
+
+    let response = send_system_patch(&ip, &body, stored_miner_auth(&app, &ip).as_ref()).await?;
+
+    if rejects_unknown_field(response.status()) {
+        return Err(MinerError::Unsupported(format!(
+            "Miner at {} rejected the display setting ({}). Its firmware may be too old to support display control",
+            ip,
+            response.status()
+        )));
+    }
+
+    settings_response_json(response, &body).await
+}
+
+// Command to make a miner visibly identify itself so it can be found on the shelf
+// Uses the firmware's identify endpoint where there is one (it picks its own duration);
+// otherwise blinks the display for `seconds` and then restores its timeout.
+// Returns "endpoint" or "display" depending on the method used
+#[tauri::command]
+async fn identify_miner(app: AppHandle, ip: String, seconds: u64) -> Result<String, MinerError> {
+    let _timer = CommandTimer::start("identify_miner", || format!("ip={:?} seconds={:?}", ip, seconds));
+    if seconds == 0 || seconds > MAX_IDENTIFY_SECS {
+        return Err(MinerError::InvalidInput(format!(
+            "Invalid identify duration: {}s. Must be 1-{}s",
+            seconds, MAX_IDENTIFY_SECS
+        )));
+    }
+
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let auth = stored_miner_auth(&app, &ip);
+    let host = connect_host(&ip).await;
+    if let Some(response) = post_first_supported(&client, &host, IDENTIFY_PATHS, auth.as_ref()).await? {
+        if !response.status().is_success() {
+            return Err(MinerError::HttpStatus(response.status().as_u16()));
+        }
+        return Ok("endpoint".to_string());
+    }
+
+    // No identify endpoint: toggle the display, then put the original timeout back
+    let info = fetch_miner_data(&app, &client, &ip, DEFAULT_FETCH_RETRIES, auth.as_ref()).await?;
+    let original_timeout = info.get("displayTimeout").and_then(|v| v.as_i64()).unwrap_or(-1);
+
+    let blinks = seconds * 1000 / IDENTIFY_BLINK_MS;
+    let mut blink_result = Ok(());
+    for blink in 0..blinks {
+        let timeout = if blink % 2 == 0 { 0 } else { -1 };
+        let body = serde_json::json!({ "displayTimeout": timeout });
+        let response = match send_system_patch_to(&client, &host, &body, auth.as_ref()).await {
+            Ok(response) => response,
+            Err(e) => {
+                blink_result = Err(e);
+                break;
+            }
+        };
+        if rejects_unknown_field(response.status()) {
+            blink_result = Err(MinerError::Unsupported(format!(
+                "Miner at {} has no identify endpoint and rejected the display setting ({})",
+                ip,
+                response.status()
+            )));
+            break;
+        }
+        if !response.status().is_success() {
+            blink_result = Err(MinerError::HttpStatus(response.status().as_u16()));
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(IDENTIFY_BLINK_MS)).await;
+    }
+
+    let restore = serde_json::json!({ "displayTimeout": original_timeout });
+    if let Err(e) = send_system_patch_to(&client, &host, &restore, auth.as_ref()).await {
+        log::warn!("Failed to restore display timeout on {}: {}", ip, e);
+    }
+
+    blink_result.map(|_| "display".to_string())
+}
+
+// Stream a firmware image to an AxeOS OTA endpoint, emitting "ota-progress" as chunks are sent
+// AxeOS expects the raw .bin as the request body (same as its own web UI uploads)
+async fn upload_ota_image(
+    app: &AppHandle,
+    ip: &str,
+    file_path: &str,
+    endpoint: &str,
+) -> Result<serde_json::Value, MinerError> {
+    let bytes = tokio::fs::read(file_path)
+        .await
+        .map_err(|e| MinerError::InvalidInput(format!("Failed to read {}: {}", file_path, e)))?;
+    let total = bytes.len();
+    if total == 0 {
+        return Err(MinerError::InvalidInput(format!("Firmware file is empty: {}", file_path)));
+    }
+
+    // OTA can take a minute, so only the connect phase is bounded
+    let client = http_client_builder()
+        .connect_timeout(Duration::from_secs(10))
+        .build()?;
+
+    let chunks: Vec<Vec<u8>> = bytes.chunks(OTA_CHUNK_SIZE).map(|c| c.to_vec()).collect();
+    let progress_app = app.clone();
+    let progress_ip = ip.to_string();
+    let mut sent = 0;
+    let body_stream = stream::iter(chunks).map(move |chunk| {
+        sent += chunk.len();
+        let _ = progress_app.emit("ota-progress", OtaProgress {
+            ip: progress_ip.clone(),
+            sent,
+            total,
+        });
+        Ok::<_, std::io::Error>(chunk)
+    });
+
+    let url = miner_url(&connect_host(ip).await, endpoint);
+    let auth = stored_miner_auth(app, ip);
+    let response = with_auth(client.post(&url), auth.as_ref())
+        .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+        .header(reqwest::header::CONTENT_LENGTH, total)
+        .body(reqwest::Body::wrap_stream(body_stream))
+        .send()
+        .await?;
+
+    if response.status().is_success() {
+        match response.json::<serde_json::Value>().await {
+            Ok(json) => Ok(json),
+            Err(_) => Ok(serde_json::json!({"success": true})),
+        }
+    } else {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        log::warn!("Firmware upload to {} failed ({}): {}", ip, status, error_text);
+        Err(MinerError::HttpStatus(status.as_u16()))
+    }
+}
+
+// Command to flash firmware (esp-miner.bin) over the air
+#[tauri::command]
+async fn upload_firmware(app: AppHandle, ip: String, file_path: String) -> Result<serde_json::Value, MinerError> {
+    let _timer = CommandTimer::start("upload_firmware", || format!("ip={:?} file_path={:?}", ip, file_path));
+    upload_ota_image(&app, &ip, &file_path, "/api/system/OTA").await
+}
+
+// Command to flash the web interface assets (www.bin) over the air
+#[tauri::command]
+async fn upload_www(app: AppHandle, ip: String, file_path: String) -> Result<serde_json::Value, MinerError> {
+    let _timer = CommandTimer::start("upload_www", || format!("ip={:?} file_path={:?}", ip, file_path));
+    upload_ota_image(&app, &ip, &file_path, "/api/system/OTAWWW").await
+}
+
+// Read a JSON body, giving up if it grows past `limit` bytes or doesn't parse
+// Protects scans from devices on the range that return huge or garbage bodies
+async fn read_json_capped(mut response: reqwest::Response, limit: usize) -> Option<serde_json::Value> {
+    if response.content_length().is_some_and(|len| len > limit as u64) {
+        return None;
+    }
+
+    let mut body = Vec::new();
+    while let Ok(Some(chunk)) = response.chunk().await {
+        if body.len() + chunk.len() > limit {
+            return None;
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    serde_json::from_slice(&body).ok()
+}
+
+// First value present under any of the given keys
+fn field_alias<'a>(raw: &'a serde_json::Value, keys: &[&str]) -> Option<&'a serde_json::Value> {
+    keys.iter().find_map(|key| raw.get(key).filter(|v| !v.is_null()))
+}
+
+// Map an AxeOS response from any known firmware version onto the canonical MinerInfo
+fn normalize_miner_json(raw: &serde_json::Value) -> MinerInfo {
+    let text = |keys: &[&str]| {
+        field_alias(raw, keys).and_then(|v| match v {
+            serde_json::Value::String(s) => Some(s.clone()),
+            serde_json::Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        })
+    };
+    // Some firmware reports numbers as strings
+    let number = |keys: &[&str]| {
+        field_alias(raw, keys).and_then(|v| v.as_f64().or_else(|| v.as_str()?.trim().parse().ok()))
+    };
+    let integer = |keys: &[&str]| number(keys).map(|n| n.max(0.0).round() as u64);
+    // Flags come as 0/1 or as booleans
+    let flag = |keys: &[&str]| {
+        field_alias(raw, keys).and_then(|v| v.as_bool().or_else(|| v.as_f64().map(|n| n != 0.0)))
+    };
+
+    MinerInfo {
+        hostname: text(&["hostname", "hostName"]),
+        version: text(&["version", "axeOSVersion", "firmwareVersion"]),
+        asic_model: text(&["ASICModel", "asicModel", "asic_model"]),
+        board_version: text(&["boardVersion", "board_version"]),
+        hash_rate: number(&["hashRate", "hashrate", "hash_rate"]),
+        expected_hashrate: number(&["expectedHashrate", "expectedHashRate"]),
+        temp: number(&["temp", "temperature", "chipTemp"]),
+        vr_temp: number(&["vrTemp", "vr_temp", "vrTemperature"]),
+        power: number(&["power", "powerW"]),
+        voltage: number(&["voltage"]),
+        current: number(&["current"]),
+        frequency: number(&["frequency", "asicFrequency"]),
+        core_voltage: number(&["coreVoltage", "core_voltage"]),
+        core_voltage_actual: number(&["coreVoltageActual", "core_voltage_actual"]),
+        fan_speed: number(&["fanspeed", "fanSpeed", "fanSpeedPercent"]),
+        fan_rpm: number(&["fanrpm", "fanRpm", "fanRPM"]),
+        shares_accepted: integer(&["sharesAccepted", "shares_accepted"]),
+        shares_rejected: integer(&["sharesRejected", "shares_rejected"]),
+        uptime_seconds: integer(&["uptimeSeconds", "uptime_seconds", "uptime"]),
+        best_diff: text(&["bestDiff", "best_diff"]),
+        best_session_diff: text(&["bestSessionDiff", "best_session_diff"]),
+        stratum_url: text(&["stratumURL", "stratumUrl", "stratum_url"]),
+        stratum_port: integer(&["stratumPort", "stratum_port"]),
+        stratum_user: text(&["stratumUser", "stratum_user"]),
+        wifi_rssi: number(&["wifiRSSI", "wifiRssi", "rssi"]),
+        ssid: text(&["ssid"]),
+        mac_addr: text(&["macAddr", "mac", "mac_addr"]),
+        ap_enabled: flag(&["apEnabled", "ap_enabled"]),
+        using_fallback_stratum: flag(&["isUsingFallbackStratum", "usingFallbackStratum"]),
+        stratum_connected: flag(&["isStratumConnected", "stratumConnected", "stratum_connected"]),
+    }
+}
+
+// Whether a JSON response carries fields only AxeOS miners report
+fn looks_like_axeos(json: &serde_json::Value) -> bool {
+    let info = normalize_miner_json(json);
+    info.asic_model.is_some() || info.hash_rate.is_some() || info.stratum_url.is_some()
+}
+
+// Check if a single IP has a miner by probing the given API paths in order
+// Hostnames are accepted too; the address they resolve to is reported as resolved_ip
+async fn check_miner_at_ip<P: AsRef<str>>(client: &reqwest::Client, ip: String, api_paths: &[P]) -> Option<DiscoveredMiner> {
+    let resolved_ip = resolve_miner_host(&ip).await;
+    let host = match &resolved_ip {
+        Some(addr) if is_mdns_name(&ip) => addr.clone(),
+        _ => ip.clone(),
+    };
+
+    for path in api_paths {
+        let url = miner_url(&host, path.as_ref());
+        match client.get(&url).send().await {
+            Ok(response) => {
+                if response.status().is_success() {
+                    if let Some(json) = read_json_capped(response, MAX_PROBE_BODY_BYTES).await {
+                        // Routers and IoT devices can serve JSON here too
+                        if !looks_like_axeos(&json) {
+                            continue;
+                        }
+
+                        // Extract miner info from response
+                        let info = normalize_miner_json(&json);
+
+                        return Some(DiscoveredMiner {
+                            ip,
+                            resolved_ip,
+                            hostname: info.hostname,
+                            version: info.version,
+                            model: info.asic_model,
+                            hash_rate: info.hash_rate,
+                            temp: info.temp,
+                            power: info.power,
+                            discovered_at: unix_now(),
+                        });
+                    }
+                }
+            }
+            Err(_) => continue,
+        }
+    }
+    None
+}
+
+// Whether a host accepts a TCP connection on port 80 within the prefilter timeout
+async fn http_port_open(ip: &str) -> bool {
+    let connect = tokio::net::TcpStream::connect((ip, 80));
+    matches!(
+        tokio::time::timeout(Duration::from_millis(SCAN_TCP_PREFILTER_TIMEOUT_MS), connect).await,
+        Ok(Ok(_))
+    )
+}
+
+// Run `probe` on every IP with at most `max_concurrent` in flight, each also holding a
+// permit from `budget`; IPs still queued once `cancelled` is set are skipped
+// Calls on_progress after each IP completes and returns the hits in IP order
+async fn probe_ips<F, Fut>(
+    ips: Vec<String>,
+    max_concurrent: usize,
+    budget: &tokio::sync::Semaphore,
+    cancelled: &AtomicBool,
+    probe: F,
+    on_progress: impl Fn(ScanProgress),
+) -> Vec<DiscoveredMiner>
+where
+    F: Fn(String) -> Fut,
+    Fut: std::future::Future<Output = Option<DiscoveredMiner>>,
+{
+    let total = ips.len();
+    let checked = AtomicUsize::new(0);
+    let found = AtomicUsize::new(0);
+
+    let tasks = ips.into_iter().map(|ip| {
+        let probe = &probe;
+        let on_progress = &on_progress;
+        let checked = &checked;
+        let found = &found;
+        async move {
+            // Skip probes still queued when the scan is cancelled
+            if cancelled.load(Ordering::SeqCst) {
+                return None;
+            }
+            // Held until the probe finishes; the semaphore is never closed
+            let permit = budget.acquire().await.ok();
+            let result = probe(ip).await;
+            drop(permit);
+
+            if result.is_some() {
+                found.fetch_add(1, Ordering::SeqCst);
+            }
+            on_progress(ScanProgress {
+                checked: checked.fetch_add(1, Ordering::SeqCst) + 1,
+                total,
+                found: found.load(Ordering::SeqCst),
+            });
+
+            result
+        }
+    });
+
+    // Run scans with bounded parallelism to avoid exhausting sockets
+    // (buffered keeps results in IP order, same as before)
+    let results: Vec<Option<DiscoveredMiner>> = stream::iter(tasks)
+        .buffered(max_concurrent.max(1))
+        .collect()
+        .await;
+
+    results.into_iter().flatten().collect()
+}
+
+// Probe every IP in the list with at most `max_concurrent` requests in flight
+// (fewer while monitoring, which draws on the same RequestBudget); stops early once `scan` is cancelled
+// With tcp_prefilter, IPs whose port 80 is closed are skipped without an HTTP request
+// Emits a "scan-progress" event after each IP completes
+#[allow(clippy::too_many_arguments)]
+async fn scan_ips(
+    app: &AppHandle,
+    client: &reqwest::Client,
+    ips: Vec<String>,
+    max_concurrent: usize,
+    api_paths: &[String],
+    tcp_prefilter: bool,
+    pass: ScanPass,
+    scan: &ScanToken<'_>,
+) -> Vec<DiscoveredMiner> {
+    let per_pass = ips.len();
+
+    probe_ips(
+        ips,
+        max_concurrent,
+        &app.state::<RequestBudget>().inner().permits,
+        &scan.cancelled,
+        |ip| async move {
+            if tcp_prefilter && !http_port_open(&ip).await {
+                None
+            } else {
+                check_miner_at_ip(client, ip, api_paths).await
+            }
+        },
+        |progress| {
+            // found stays per pass, since later passes mostly re-find the same miners
+            let progress = ScanProgress {
+                checked: pass.index * per_pass + progress.checked,
+                total: pass.count * per_pass,
+                found: progress.found,
+            };
+            let _ = app.emit("scan-progress", progress);
+        },
+    )
+    .await
+}
+
+// Remember when the last scan ran and what it found, for last_scan_info
+fn record_last_scan(app: &AppHandle, subnet: &str, count: usize) {
+    let info = LastScanInfo {
+        at: unix_now(),
+        count,
+        subnet: subnet.to_string(),
+    };
+    let Ok(store) = app.store(APP_SETTINGS_STORE_FILE) else {
+        return;
+    };
+    if let Ok(value) = serde_json::to_value(&info) {
+        store.set(LAST_SCAN_KEY, value);
+        if let Err(e) = store.save() {
+            log::warn!("Failed to save last scan info: {}", e);
+        }
+    }
+}
+
+// Check a scan timeout is within the allowed range
+fn validate_scan_timeout(timeout_ms: u64) -> Result<(), MinerError> {
+    if !(MIN_SCAN_TIMEOUT_MS..=MAX_SCAN_TIMEOUT_MS).contains(&timeout_ms) {
+        return Err(MinerError::InvalidInput(format!(
+            "Invalid scan timeout: {}ms. Must be between {}ms and {}ms",
+            timeout_ms, MIN_SCAN_TIMEOUT_MS, MAX_SCAN_TIMEOUT_MS
+        )));
+    }
+    Ok(())
+}
+
+// Create a client with short timeout for scanning
+// Defaults to 1500ms; values outside the allowed range are rejected
+fn build_scan_client(timeout_ms: Option<u64>) -> Result<reqwest::Client, MinerError> {
+    let timeout_ms = timeout_ms.unwrap_or(DEFAULT_SCAN_TIMEOUT_MS);
+    validate_scan_timeout(timeout_ms)?;
+
+    http_client_builder()
+        .timeout(Duration::from_millis(timeout_ms))
+        .build()
+        .map_err(MinerError::from)
+}
+
+// IPs from subnet.start to subnet.end, for a subnet given as its first three octets (e.g. "192.168.1")
+fn subnet_range_ips(subnet: &str, start: u8, end: u8) -> Result<Vec<String>, MinerError> {
+    let parts: Vec<&str> = subnet.split('.').collect();
+    if parts.len() != 3 {
+        return Err(MinerError::InvalidInput("Invalid subnet format. Expected format: 192.168.1".to_string()));
+    }
+    for part in &parts {
+        if part.parse::<u8>().is_err() {
+            return Err(MinerError::InvalidInput(format!("Invalid subnet octet: {}", part)));
+        }
+    }
+
+    Ok((start..=end).map(|i| format!("{}.{}", subnet, i)).collect())
+}
+
+// Command to scan network for miners
+// paths overrides the API paths probed on each IP (see resolve_api_paths)
+// tcp_prefilter skips the HTTP probe on IPs with port 80 closed, which speeds up sparse ranges
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn scan_network(
+    app: AppHandle,
+    subnet: String,
+    start: u8,
+    end: u8,
+    max_concurrent: Option<usize>,
+    timeout_ms: Option<u64>,
+    paths: Option<Vec<String>>,
+    tcp_prefilter: Option<bool>,
+) -> Result<Vec<DiscoveredMiner>, MinerError> {
+    let _timer = CommandTimer::start("scan_network", || {
+        format!(
+            "subnet={:?} start={:?} end={:?} max_concurrent={:?} timeout_ms={:?} paths={:?} tcp_prefilter={:?}",
+            subnet, start, end, max_concurrent, timeout_ms, paths, tcp_prefilter
+        )
+    });
+    let scan = app.state::<ScanState>().inner().begin();
+    let ips = subnet_range_ips(&subnet, start, end)?;
+    let client = build_scan_client(timeout_ms)?;
+
+    let limit = max_concurrent.unwrap_or(DEFAULT_SCAN_CONCURRENCY);
+    let api_paths = resolve_api_paths(&app, paths, DEFAULT_PROBE_PATHS);
+
+    let found = scan_ips(&app, &client, ips, limit, &api_paths, tcp_prefilter.unwrap_or(false), ScanPass::SINGLE, &scan).await;
+    record_last_scan(&app, &subnet, found.len());
+
+    // Only a scan that ran to the end is worth repeating
+    if scan.is_cancelled() {
+        return Ok(found);
+    }
+    let params = ScanParams {
+        subnet,
+        start,
+        end,
+        timeout_ms: timeout_ms.unwrap_or(DEFAULT_SCAN_TIMEOUT_MS),
+    };
+    if let (Ok(store), Ok(value)) = (app.store(APP_SETTINGS_STORE_FILE), serde_json::to_value(&params)) {
+        store.set(LAST_SCAN_PARAMS_KEY, value);
+        if let Err(e) = store.save() {
+            log::warn!("Failed to save last scan params: {}", e);
+        }
+    }
+    Ok(found)
+}
+
+// Command to get the range and timeout of the last completed (not cancelled) scan_network call, if any
+#[tauri::command]
+async fn get_last_scan_params(app: AppHandle) -> Result<Option<ScanParams>, String> {
+    let _timer = CommandTimer::start("get_last_scan_params", String::new);
+    let store = app.store(APP_SETTINGS_STORE_FILE).map_err(|e| e.to_string())?;
+    Ok(store
+        .get(LAST_SCAN_PARAMS_KEY)
+        .and_then(|value| serde_json::from_value(value).ok()))
+}
+
+// Command to get when the last scan ran, how many miners it found and which range it covered
+// Returns None until the first scan completes
+#[tauri::command]
+async fn last_scan_info(app: AppHandle) -> Result<Option<LastScanInfo>, String> {
+    let _timer = CommandTimer::start("last_scan_info", String::new);
+    let store = app.store(APP_SETTINGS_STORE_FILE).map_err(|e| e.to_string())?;
+    Ok(store
+        .get(LAST_SCAN_KEY)
+        .and_then(|value| serde_json::from_value(value).ok()))
+}
+
+// Command to scan a subnet range several times and union the results
+// Each miner reports how many passes it answered, which separates flaky miners from absent IPs;
+// passes is capped at MAX_SCAN_PASSES and cancel_scan stops the remaining passes
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn scan_network_thorough(
+    app: AppHandle,
+    subnet: String,
+    start: u8,
+    end: u8,
+    passes: u32,
+    max_concurrent: Option<usize>,
+    timeout_ms: Option<u64>,
+    paths: Option<Vec<String>>,
+) -> Result<Vec<ThoroughScanMiner>, MinerError> {
+    let _timer = CommandTimer::start("scan_network_thorough", || {
+        format!(
+            "subnet={:?} start={:?} end={:?} passes={:?} max_concurrent={:?} timeout_ms={:?} paths={:?}",
+            subnet, start, end, passes, max_concurrent, timeout_ms, paths
+        )
+    });
+    let scan = app.state::<ScanState>().inner().begin();
+    if !(1..=MAX_SCAN_PASSES).contains(&passes) {
+        return Err(MinerError::InvalidInput(format!(
+            "Invalid pass count: {}. Must be between 1 and {}",
+            passes, MAX_SCAN_PASSES
+        )));
+    }
+
+    let ips = subnet_range_ips(&subnet, start, end)?;
+    let client = build_scan_client(timeout_ms)?;
+    let limit = max_concurrent.unwrap_or(DEFAULT_SCAN_CONCURRENCY);
+    let api_paths = resolve_api_paths(&app, paths, DEFAULT_PROBE_PATHS);
+
+    let mut miners: Vec<ThoroughScanMiner> = Vec::new();
+    let mut completed = 0;
+    for pass in 0..passes {
+        if scan.is_cancelled() {
+            break;
+        }
+        let pass = ScanPass {
+            index: pass as usize,
+            count: passes as usize,
+        };
+        let found = scan_ips(&app, &client, ips.clone(), limit, &api_paths, false, pass, &scan).await;
+        completed += 1;
+
+        // Keep the latest reading of each miner
+        for miner in found {
+            match miners.iter_mut().find(|entry| entry.miner.ip == miner.ip) {
+                Some(entry) => {
+                    entry.miner = miner;
+                    entry.responded_passes += 1;
+                }
+                None => miners.push(ThoroughScanMiner {
+                    miner,
+                    responded_passes: 1,
+                    passes: 0,
+                }),
+            }
+        }
+    }
+
+    for entry in &mut miners {
+        entry.passes = completed;
+    }
+    // Same order as a single scan
+    miners.sort_by_key(|entry| entry.miner.ip.parse::<Ipv4Addr>().ok());
+    record_last_scan(&app, &subnet, miners.len());
+    Ok(miners)
+}
+
+// Command to scan a subnet range and return the full system info of every miner found
+// Both the scan and the follow-up fetches are bounded; a miner whose full fetch fails
+// is still returned with the summary the scan saw
+#[tauri::command]
+async fn scan_and_enrich(
+    app: AppHandle,
+    subnet: String,
+    start: u8,
+    end: u8,
+    max_concurrent: Option<usize>,
+    timeout_ms: Option<u64>,
+) -> Result<Vec<serde_json::Value>, MinerError> {
+    let _timer = CommandTimer::start("scan_and_enrich", || {
+        format!(
+            "subnet={:?} start={:?} end={:?} max_concurrent={:?} timeout_ms={:?}",
+            subnet, start, end, max_concurrent, timeout_ms
+        )
+    });
+    // Registered before the scan so a cancel during either phase skips the enrichment
+    let scan = app.state::<ScanState>().inner().begin();
+    let found = scan_network(app.clone(), subnet, start, end, max_concurrent, timeout_ms, None, None).await?;
+    if scan.is_cancelled() {
+        return Ok(found
+            .into_iter()
+            .map(|miner| serde_json::to_value(&miner).unwrap_or_default())
+            .collect());
+    }
+    let results = fetch_many_miners(&app, found.iter().map(|miner| miner.ip.clone()).collect()).await?;
+
+    let miners = found
+        .into_iter()
+        .zip(results)
+        .map(|(miner, result)| {
+            let mut data = match result.data {
+                Some(data) => data,
+                None => serde_json::to_value(&miner).unwrap_or_default(),
+            };
+            if let Some(obj) = data.as_object_mut() {
+                obj.insert("ip_address".to_string(), serde_json::Value::from(miner.ip));
+            }
+            data
+        })
+        .collect();
+
+    Ok(miners)
+}
+
+// Command to scan a CIDR block for miners (e.g., "192.168.0.0/23")
+// Masks wider than /16 are rejected to avoid accidental huge scans
+// tcp_prefilter works as in scan_network
+#[tauri::command]
+async fn scan_network_cidr(
+    app: AppHandle,
+    cidr: String,
+    max_concurrent: Option<usize>,
+    timeout_ms: Option<u64>,
+    paths: Option<Vec<String>>,
+    tcp_prefilter: Option<bool>,
+) -> Result<Vec<DiscoveredMiner>, MinerError> {
+    let _timer = CommandTimer::start("scan_network_cidr", || {
+        format!(
+            "cidr={:?} max_concurrent={:?} timeout_ms={:?} paths={:?} tcp_prefilter={:?}",
+            cidr, max_concurrent, timeout_ms, paths, tcp_prefilter
+        )
+    });
+    let scan = app.state::<ScanState>().inner().begin();
+    let net: Ipv4Net = cidr
+        .trim()
+        .parse()
+        .map_err(|_| MinerError::InvalidInput(format!("Invalid CIDR format: {}. Expected format: 192.168.0.0/23", cidr)))?;
+
+    if net.prefix_len() < MIN_CIDR_PREFIX {
+        return Err(MinerError::InvalidInput(format!(
+            "CIDR range /{} is too large. Use /{} or smaller",
+            net.prefix_len(),
+            MIN_CIDR_PREFIX
+        )));
+    }
+
+    let client = build_scan_client(timeout_ms)?;
+
+    // Iterate all host addresses (network and broadcast are skipped)
+    let ips: Vec<String> = net.hosts().map(|ip| ip.to_string()).collect();
+
+    let limit = max_concurrent.unwrap_or(DEFAULT_SCAN_CONCURRENCY);
+    let api_paths = resolve_api_paths(&app, paths, DEFAULT_PROBE_PATHS);
+
+    let found = scan_ips(&app, &client, ips, limit, &api_paths, tcp_prefilter.unwrap_or(false), ScanPass::SINGLE, &scan).await;
+    record_last_scan(&app, &net.to_string(), found.len());
+    Ok(found)
+}
+
+// Command to discover miners via mDNS/Bonjour advertisements
+// Finds miners on any subnet the host can hear, then verifies each over HTTP
+#[tauri::command]
+async fn discover_mdns(app: AppHandle, timeout_secs: u64) -> Result<Vec<DiscoveredMiner>, MinerError> {
+    let _timer = CommandTimer::start("discover_mdns", || format!("timeout_secs={:?}", timeout_secs));
+    let mdns = ServiceDaemon::new().map_err(|e| MinerError::Internal(format!("Failed to start mDNS: {}", e)))?;
+    let receiver = mdns
+        .browse(MDNS_SERVICE_TYPE)
+        .map_err(|e| MinerError::Internal(format!("Failed to browse mDNS: {}", e)))?;
+
+    // Collect unique IPv4 addresses until the timeout elapses
+    let mut ips: Vec<String> = Vec::new();
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(timeout_secs.max(1));
+    while let Ok(Ok(event)) = tokio::time::timeout_at(deadline, receiver.recv_async()).await {
+        if let ServiceEvent::ServiceResolved(info) = event {
+            for addr in info.get_addresses() {
+                if let IpAddr::V4(ipv4) = addr {
+                    let ip = ipv4.to_string();
+                    if !ips.contains(&ip) {
+                        ips.push(ip);
+                    }
+                }
+            }
+        }
+    }
+    let _ = mdns.shutdown();
+
+    // Only keep hosts that actually answer the AxeOS API
+    let client = build_scan_client(None)?;
+    let api_paths = resolve_api_paths(&app, None, DEFAULT_PROBE_PATHS);
+    let tasks = ips.into_iter().map(|ip| {
+        let client = &client;
+        let api_paths = &api_paths;
+        let app = &app;
+        async move {
+            let _permit = app.state::<RequestBudget>().inner().permits.acquire().await.ok();
+            check_miner_at_ip(client, ip, api_paths).await
+        }
+    });
+    let miners = join_all(tasks).await.into_iter().flatten().collect();
+
+    Ok(miners)
+}
+
+// Peer addresses from an AxeOS /api/swarm/info response
+// Accepts a bare list or one wrapped in "swarm"/"peers", of strings or objects carrying "ip"/"address"
+fn parse_swarm_peers(json: &serde_json::Value) -> Vec<String> {
+    let list = json
+        .as_array()
+        .or_else(|| json.get("swarm").and_then(|v| v.as_array()))
+        .or_else(|| json.get("peers").and_then(|v| v.as_array()));
+
+    let mut peers: Vec<String> = Vec::new();
+    for entry in list.into_iter().flatten() {
+        let peer = match entry {
+            serde_json::Value::String(s) => Some(s.as_str()),
+            _ => field_alias(entry, &["ip", "address", "host"]).and_then(|v| v.as_str()),
+        };
+        if let Some(peer) = peer.map(str::trim).filter(|p| validate_miner_target(p).is_ok()) {
+            if !peers.iter().any(|p| p == peer) {
+                peers.push(peer.to_string());
+            }
+        }
+    }
+    peers
+}
+
+// Command to discover miners from one known miner's swarm list
+// Each listed peer (and the seed itself) is probed like a scan hit, so miners on other VLANs are found
+#[tauri::command]
+async fn discover_via_swarm(app: AppHandle, seed_ip: String) -> Result<Vec<DiscoveredMiner>, MinerError> {
+    let _timer = CommandTimer::start("discover_via_swarm", || format!("seed_ip={:?}", seed_ip));
+    let client = build_scan_client(None)?;
+
+    let url = miner_url(&connect_host(&seed_ip).await, "/api/swarm/info");
+    let permit = app.state::<RequestBudget>().inner().permits.acquire().await.ok();
+    let response = client.get(&url).send().await?;
+    if !response.status().is_success() {
+        return Err(MinerError::HttpStatus(response.status().as_u16()));
+    }
+    let json = read_json_capped(response, MAX_PROBE_BODY_BYTES)
+        .await
+        .ok_or(MinerError::ParseError)?;
+    drop(permit);
+
+    let mut targets = vec![seed_ip];
+    for peer in parse_swarm_peers(&json) {
+        if !targets.contains(&peer) {
+            targets.push(peer);
+        }
+    }
+
+    let api_paths = resolve_api_paths(&app, None, DEFAULT_PROBE_PATHS);
+    let tasks = targets.into_iter().map(|ip| {
+        let client = &client;
+        let api_paths = &api_paths;
+        let app = &app;
+        async move {
+            let _permit = app.state::<RequestBudget>().inner().permits.acquire().await.ok();
+            check_miner_at_ip(client, ip, api_paths).await
+        }
+    });
+    let miners = join_all(tasks).await.into_iter().flatten().collect();
+
+    Ok(miners)
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct DiscoveredMiner {
+// Command to cancel an in-progress network scan
+// The running scan returns whatever it found so far
+#[tauri::command]
+async fn cancel_scan(state: State<'_, ScanState>) -> Result<(), String> {
+    let _timer = CommandTimer::start("cancel_scan", String::new);
+    state.cancel_all();
+    Ok(())
+}
+
+// Command to list every private /24 the host is attached to (e.g. wifi and ethernet)
+// Docker, VM and Hyper-V adapters are skipped
+#[tauri::command]
+async fn get_all_local_subnets() -> Result<Vec<String>, String> {
+    let _timer = CommandTimer::start("get_all_local_subnets", String::new);
+    let interfaces = local_ip_address::list_afinet_netifas()
+        .map_err(|e| format!("Failed to get network interfaces: {}", e))?;
+
+    let mut subnets = Vec::new();
+    for (name, ip) in interfaces {
+        let IpAddr::V4(ipv4) = ip else {
+            continue;
+        };
+        let lower_name = name.to_lowercase();
+        if !ipv4.is_private() || VIRTUAL_INTERFACE_PREFIXES.iter().any(|p| lower_name.starts_with(p)) {
+            continue;
+        }
+        let octets = ipv4.octets();
+        // Docker's default bridge network
+        if octets[0] == 172 && octets[1] == 17 {
+            continue;
+        }
+        let subnet = format!("{}.{}.{}", octets[0], octets[1], octets[2]);
+        if !subnets.contains(&subnet) {
+            subnets.push(subnet);
+        }
+    }
+
+    Ok(subnets)
+}
+
+// Command to save discovered miners so they survive restarts
+// Existing rows are updated in place and their last_seen refreshed
+#[tauri::command]
+async fn save_discovered_miners(
+    db: State<'_, Database>,
+    miners: Vec<DiscoveredMiner>,
+) -> Result<(), String> {
+    let _timer = CommandTimer::start("save_discovered_miners", || format!("miners={}", miners.len()));
+    let now = unix_now();
+    let mut tx = db.pool()?.begin().await.map_err(|e| e.to_string())?;
+
+    for miner in &miners {
+        sqlx::query(
+            "INSERT INTO miners (ip, hostname, version, model, last_seen)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(ip) DO UPDATE SET
+               hostname = excluded.hostname,
+               version = excluded.version,
+               model = excluded.model,
+               last_seen = excluded.last_seen",
+        )
+        .bind(&miner.ip)
+        .bind(&miner.hostname)
+        .bind(&miner.version)
+        .bind(&miner.model)
+        .bind(now)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+
+    tx.commit().await.map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+// Command to load previously saved miners, most recently seen first
+#[tauri::command]
+async fn load_saved_miners(db: State<'_, Database>) -> Result<Vec<DiscoveredMiner>, String> {
+    let _timer = CommandTimer::start("load_saved_miners", String::new);
+    let rows: Vec<SavedMinerRow> = sqlx::query_as(
+        "SELECT ip, hostname, version, model, last_seen FROM miners ORDER BY last_seen DESC",
+    )
+    .fetch_all(db.pool()?)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let miners = rows
+        .into_iter()
+        .map(|(ip, hostname, version, model, last_seen)| DiscoveredMiner {
+            ip,
+            resolved_ip: None,
+            hostname,
+            version,
+            model,
+            hash_rate: None,
+            temp: None,
+            power: None,
+            discovered_at: last_seen,
+        })
+        .collect();
+
+    Ok(miners)
+}
+
+// Command to record a time-series sample from an AxeOS data response
+// Missing or non-numeric fields are stored as NULL
+#[tauri::command]
+async fn log_miner_sample(
+    db: State<'_, Database>,
     ip: String,
-    hostname: Option<String>,
-    version: Option<String>,
-    model: Option<String>,
+    sample: serde_json::Value,
+) -> Result<(), String> {
+    let _timer = CommandTimer::start("log_miner_sample", || format!("ip={:?}", ip));
+    let field = |key: &str| sample.get(key).and_then(|v| v.as_f64());
+    let count = |key: &str| sample.get(key).and_then(|v| v.as_i64());
+
+    sqlx::query(
+        "INSERT INTO samples
+           (ip, timestamp, hashrate, temp, power, frequency, core_voltage, shares_accepted, shares_rejected)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&ip)
+    .bind(unix_now())
+    .bind(field("hashRate"))
+    .bind(field("temp"))
+    .bind(field("power"))
+    .bind(field("frequency"))
+    .bind(field("coreVoltage"))
+    .bind(count("sharesAccepted"))
+    .bind(count("sharesRejected"))
+    .execute(db.pool()?)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
 }
 
-// Command to fetch miner data
+// Read sample rows for a miner between two unix timestamps (inclusive), oldest first
+async fn fetch_sample_rows(
+    pool: &SqlitePool,
+    ip: &str,
+    since_unix: i64,
+    until_unix: i64,
+) -> Result<Vec<SampleRow>, String> {
+    sqlx::query_as(
+        "SELECT timestamp, hashrate, temp, power, frequency, core_voltage, shares_accepted, shares_rejected
+         FROM samples
+         WHERE ip = ? AND timestamp BETWEEN ? AND ?
+         ORDER BY timestamp ASC",
+    )
+    .bind(ip)
+    .bind(since_unix)
+    .bind(until_unix)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+// Command to read samples for a miner between two unix timestamps (inclusive)
+#[tauri::command]
+async fn query_samples(
+    db: State<'_, Database>,
+    ip: String,
+    since_unix: i64,
+    until_unix: i64,
+) -> Result<Vec<serde_json::Value>, String> {
+    let _timer = CommandTimer::start("query_samples", || {
+        format!("ip={:?} since_unix={:?} until_unix={:?}", ip, since_unix, until_unix)
+    });
+    let rows = fetch_sample_rows(db.pool()?, &ip, since_unix, until_unix).await?;
+
+    let samples = rows
+        .into_iter()
+        .map(|(timestamp, hashrate, temp, power, frequency, core_voltage, accepted, rejected)| {
+            serde_json::json!({
+                "timestamp": timestamp,
+                "hashRate": hashrate,
+                "temp": temp,
+                "power": power,
+                "frequency": frequency,
+                "coreVoltage": core_voltage,
+                "sharesAccepted": accepted,
+                "sharesRejected": rejected,
+            })
+        })
+        .collect();
+
+    Ok(samples)
+}
+
+// Command to get a miner's efficiency over time, averaged into buckets of bucket_secs
+// Each bucket is total power over total hashrate, so a brief spike doesn't dominate;
+// buckets with no usable samples are left out
+#[tauri::command]
+async fn efficiency_series(
+    db: State<'_, Database>,
+    ip: String,
+    since_unix: i64,
+    bucket_secs: i64,
+) -> Result<Vec<EfficiencyPoint>, String> {
+    let _timer = CommandTimer::start("efficiency_series", || {
+        format!("ip={:?} since_unix={:?} bucket_secs={:?}", ip, since_unix, bucket_secs)
+    });
+    if bucket_secs <= 0 {
+        return Err(format!("Invalid bucket size: {}s. Must be positive", bucket_secs));
+    }
+
+    // hashrate is GH/s, so W / (GH/s / 1000) gives J/TH
+    let rows: Vec<(i64, f64)> = sqlx::query_as(
+        "SELECT (timestamp / ?1) * ?1 AS bucket, SUM(power) / (SUM(hashrate) / 1000.0)
+         FROM samples
+         WHERE ip = ?2 AND timestamp >= ?3 AND hashrate > 0 AND power IS NOT NULL
+         GROUP BY bucket
+         ORDER BY bucket ASC",
+    )
+    .bind(bucket_secs)
+    .bind(&ip)
+    .bind(since_unix)
+    .fetch_all(db.pool()?)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let series = rows
+        .into_iter()
+        .map(|(t, j_per_th)| EfficiencyPoint { t, j_per_th })
+        .collect();
+
+    Ok(series)
+}
+
+// Command to get min/max/avg hashrate, temp and power for a miner since a unix timestamp
+// Aggregated in SQL so the frontend doesn't pull every raw sample
+#[tauri::command]
+async fn sample_rollup(db: State<'_, Database>, ip: String, since_unix: i64) -> Result<SampleRollup, String> {
+    let _timer = CommandTimer::start("sample_rollup", || format!("ip={:?} since_unix={:?}", ip, since_unix));
+    // SQLite aggregates skip NULLs and return NULL over an empty range
+    type RollupRow = (
+        Option<f64>,
+        Option<f64>,
+        Option<f64>,
+        Option<f64>,
+        Option<f64>,
+        Option<f64>,
+        Option<f64>,
+        Option<f64>,
+        Option<f64>,
+    );
+    let row: RollupRow = sqlx::query_as(
+        "SELECT MIN(hashrate), MAX(hashrate), AVG(hashrate),
+                MIN(temp), MAX(temp), AVG(temp),
+                MIN(power), MAX(power), AVG(power)
+         FROM samples
+         WHERE ip = ? AND timestamp >= ?",
+    )
+    .bind(&ip)
+    .bind(since_unix)
+    .fetch_one(db.pool()?)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let (hashrate_min, hashrate_max, hashrate_avg, temp_min, temp_max, temp_avg, power_min, power_max, power_avg) = row;
+    Ok(SampleRollup {
+        hashrate: Rollup { min: hashrate_min, max: hashrate_max, avg: hashrate_avg },
+        temp: Rollup { min: temp_min, max: temp_max, avg: temp_avg },
+        power: Rollup { min: power_min, max: power_max, avg: power_avg },
+    })
+}
+
+// Command to read a miner's event history since a unix timestamp, newest first
+#[tauri::command]
+async fn query_events(db: State<'_, Database>, ip: String, since_unix: i64) -> Result<Vec<MinerEvent>, String> {
+    let _timer = CommandTimer::start("query_events", || format!("ip={:?} since_unix={:?}", ip, since_unix));
+    let rows: Vec<(i64, String, i64, String, Option<String>)> = sqlx::query_as(
+        "SELECT id, ip, timestamp, kind, detail FROM events
+         WHERE ip = ? AND timestamp >= ?
+         ORDER BY timestamp DESC, id DESC",
+    )
+    .bind(&ip)
+    .bind(since_unix)
+    .fetch_all(db.pool()?)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let events = rows
+        .into_iter()
+        .map(|(id, ip, timestamp, kind, detail)| MinerEvent {
+            id,
+            ip,
+            timestamp,
+            kind,
+            detail: detail.filter(|d| !d.is_empty()),
+        })
+        .collect();
+
+    Ok(events)
+}
+
+// Command to count the restarts logged for a miner since a unix timestamp
+// Only restarts issued by the app are logged, not crashes or power loss
+#[tauri::command]
+async fn restart_count(db: State<'_, Database>, ip: String, since_unix: i64) -> Result<u64, String> {
+    let _timer = CommandTimer::start("restart_count", || format!("ip={:?} since_unix={:?}", ip, since_unix));
+    let (count,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM events
+         WHERE ip = ? AND timestamp >= ? AND kind = ?",
+    )
+    .bind(&ip)
+    .bind(since_unix)
+    .bind(MinerEventKind::Restarted.as_str())
+    .fetch_one(db.pool()?)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(count.max(0) as u64)
+}
+
+// Command to export a miner's samples to a CSV file
+// Returns the number of rows written
+#[tauri::command]
+async fn export_samples_csv(
+    db: State<'_, Database>,
+    ip: String,
+    path: String,
+    since_unix: i64,
+    until_unix: i64,
+) -> Result<usize, String> {
+    let _timer = CommandTimer::start("export_samples_csv", || {
+        format!(
+            "ip={:?} path={:?} since_unix={:?} until_unix={:?}",
+            ip, path, since_unix, until_unix
+        )
+    });
+    let rows = fetch_sample_rows(db.pool()?, &ip, since_unix, until_unix).await?;
+
+    let mut writer = csv::Writer::from_path(&path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+    writer
+        .write_record(["timestamp", "hashrate", "temp", "power", "frequency", "voltage", "accepted", "rejected"])
+        .map_err(|e| e.to_string())?;
+
+    // Missing values are written as empty cells
+    let cell = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+    let count = |value: Option<i64>| value.map(|v| v.to_string()).unwrap_or_default();
+
+    for (timestamp, hashrate, temp, power, frequency, core_voltage, accepted, rejected) in &rows {
+        writer
+            .write_record([
+                timestamp.to_string(),
+                cell(*hashrate),
+                cell(*temp),
+                cell(*power),
+                cell(*frequency),
+                cell(*core_voltage),
+                count(*accepted),
+                count(*rejected),
+            ])
+            .map_err(|e| e.to_string())?;
+    }
+
+    writer.flush().map_err(|e| e.to_string())?;
+    Ok(rows.len())
+}
+
+// Remember how long a successful poll of a miner took
+fn record_latency(app: &AppHandle, ip: &str, latency: Duration) {
+    let Ok(mut samples) = app.state::<LatencyState>().inner().samples.lock() else {
+        return;
+    };
+    let samples = samples.entry(ip.to_string()).or_default();
+    if samples.len() == LATENCY_SAMPLE_CAPACITY {
+        samples.pop_front();
+    }
+    samples.push_back(latency.as_millis() as u64);
+}
+
+// Command to get response-time percentiles of a miner over the current monitoring session
+// Samples come from successful monitor polls and are reset by start_monitoring
 #[tauri::command]
-async fn get_miner_data(ip: String) -> Result<serde_json::Value, String> {
-    let client = reqwest::Client::builder()
+async fn latency_stats(state: State<'_, LatencyState>, ip: String) -> Result<LatencyStats, String> {
+    let _timer = CommandTimer::start("latency_stats", || format!("ip={:?}", ip));
+    let mut sorted: Vec<u64> = match state.samples.lock().map_err(|e| e.to_string())?.get(&ip) {
+        Some(samples) => samples.iter().copied().collect(),
+        None => return Ok(LatencyStats::default()),
+    };
+    if sorted.is_empty() {
+        return Ok(LatencyStats::default());
+    }
+    sorted.sort_unstable();
+
+    // Nearest-rank percentile
+    let percentile = |p: f64| sorted[((p * sorted.len() as f64).ceil() as usize).clamp(1, sorted.len()) - 1];
+    Ok(LatencyStats {
+        p50: percentile(0.50),
+        p95: percentile(0.95),
+        max: sorted[sorted.len() - 1],
+        count: sorted.len(),
+    })
+}
+
+// Command to start polling a set of miners in the background
+// Emits a "miner-update" event per miner each cycle, staggered across the interval;
+// replaces any running monitor and resets the latency_stats samples.
+// Miners coming online or going offline (after a few missed polls) trigger a notification
+// and a "miner-presence" event
+#[tauri::command]
+async fn start_monitoring(
+    app: AppHandle,
+    state: State<'_, MonitorState>,
+    ips: Vec<String>,
+    interval_secs: u64,
+) -> Result<(), String> {
+    let _timer = CommandTimer::start("start_monitoring", || {
+        format!("ips={:?} interval_secs={:?}", ips, interval_secs)
+    });
+    let client = http_client_builder()
         .timeout(Duration::from_secs(10))
         .build()
         .map_err(|e| e.to_string())?;
 
-    let api_paths = vec![
-        "/api/system/info",
-        "/api/system",
-        "/api/swarm/info",
-    ];
+    let interval = Duration::from_secs(interval_secs.max(MIN_MONITOR_INTERVAL_SECS));
+    let stagger = interval / ips.len().max(1) as u32;
+    app.state::<LatencyState>().samples.lock().map_err(|e| e.to_string())?.clear();
+    state.latest.lock().map_err(|e| e.to_string())?.clear();
 
-    for path in api_paths {
-        let url = format!("http://{}{}", ip, path);
-        match client.get(&url).send().await {
-            Ok(response) => {
-                if response.status().is_success() {
-                    match response.json::<serde_json::Value>().await {
-                        Ok(json) => return Ok(json),
-                        Err(_) => continue,
+    let mut shutdown = shutdown_signal(&app);
+    let handle = tauri::async_runtime::spawn(async move {
+        // Last reported presence per miner, and consecutive failed polls
+        let mut online: HashMap<String, bool> = HashMap::new();
+        let mut misses: HashMap<String, u32> = HashMap::new();
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            if !next_tick(&mut ticker, &mut shutdown).await {
+                break;
+            }
+            if is_paused(&app) {
+                continue;
+            }
+
+            // Spread the polls evenly across the interval so they don't all hit the network at once
+            let polls = ips.iter().enumerate().map(|(index, ip)| {
+                let client = &client;
+                let app = &app;
+                async move {
+                    tokio::time::sleep(stagger * index as u32).await;
+                    // Share the request budget with any running scan
+                    let permit = app.state::<RequestBudget>().inner().permits.acquire().await.ok();
+                    // No retries here, the next cycle polls again anyway
+                    let started = Instant::now();
+                    let result = fetch_miner_data(app, client, ip, 0, None).await;
+                    drop(permit);
+                    if result.is_ok() {
+                        record_latency(app, ip, started.elapsed());
+                    }
+                    record_fetch_result(app, ip, &result);
+                    let update = MinerDataResult::new(ip.clone(), result);
+                    let _ = app.emit("miner-update", &update);
+                    if let Ok(mut latest) = app.state::<MonitorState>().latest.lock() {
+                        let entry = MonitorSnapshotEntry {
+                            result: update.clone(),
+                            updated_at: unix_now(),
+                        };
+                        latest.insert(ip.clone(), entry);
                     }
+                    update
+                }
+            });
+            let updates = join_all(polls).await;
+
+            // Report miners that changed presence; the first sighting of a miner just sets its state
+            let mut delta = PresenceDelta::default();
+            for update in &updates {
+                if update.data.is_some() {
+                    misses.remove(&update.ip);
+                    if online.insert(update.ip.clone(), true) == Some(false) {
+                        delta.online.push(update.ip.clone());
+                    }
+                    continue;
+                }
+                let missed = misses.entry(update.ip.clone()).or_insert(0);
+                *missed += 1;
+                if *missed >= PRESENCE_OFFLINE_AFTER_MISSES && online.insert(update.ip.clone(), false) == Some(true) {
+                    delta.offline.push(update.ip.clone());
                 }
             }
-            Err(_) => continue,
+            if !delta.online.is_empty() || !delta.offline.is_empty() {
+                for ip in &delta.online {
+                    let _ = app
+                        .notification()
+                        .builder()
+                        .title("Miner online")
+                        .body(format!("Miner {} came online", ip))
+                        .show();
+                }
+                for ip in &delta.offline {
+                    let _ = app
+                        .notification()
+                        .builder()
+                        .title("Miner offline")
+                        .body(format!("Miner {} went offline", ip))
+                        .show();
+                }
+                let _ = app.emit("miner-presence", &delta);
+            }
+
+            // Show the swarm total in the tray tooltip
+            let hashrates: Vec<f64> = updates
+                .iter()
+                .filter_map(|u| u.data.as_ref()?.get("hashRate")?.as_f64())
+                .collect();
+            let tooltip = format!(
+                "Swarm: {} · {} miners",
+                format_hashrate(hashrates.iter().sum()),
+                hashrates.len()
+            );
+            let tray = app.state::<TrayIcon>();
+            let _ = tray.set_tooltip(Some(tooltip));
+
+            // Reflect swarm health in the tray icon
+            if let Ok(icon) = tray_status_icon(swarm_status(&updates)) {
+                let _ = tray.set_icon(Some(icon));
+            }
         }
+    });
+
+    // Swap in the new task and stop the previous one
+    let previous = state.task.lock().map_err(|e| e.to_string())?.replace(handle);
+    if let Some(previous) = previous {
+        previous.abort();
     }
 
-    Err(format!("Failed to connect to miner at {}", ip))
+    Ok(())
 }
 
-// Command to restart miner
+// Command to get the latest monitor poll of every monitored miner in one call
+// Lets a newly opened window render from cache before live "miner-update" events arrive
+#[tauri::command]
+async fn snapshot(state: State<'_, MonitorState>) -> Result<Vec<MonitorSnapshotEntry>, String> {
+    let _timer = CommandTimer::start("snapshot", String::new);
+    let mut entries: Vec<MonitorSnapshotEntry> = state.latest.lock().map_err(|e| e.to_string())?.values().cloned().collect();
+    entries.sort_by_key(|entry| entry.result.ip.parse::<Ipv4Addr>().ok());
+    Ok(entries)
+}
+
+// Command to stop the background monitor
+#[tauri::command]
+async fn stop_monitoring(state: State<'_, MonitorState>) -> Result<(), String> {
+    let _timer = CommandTimer::start("stop_monitoring", String::new);
+    if let Some(handle) = state.task.lock().map_err(|e| e.to_string())?.take() {
+        handle.abort();
+    }
+    // snapshot only serves miners that are still being polled
+    state.latest.lock().map_err(|e| e.to_string())?.clear();
+    Ok(())
+}
+
+// Command to start the temperature watchdog
+// Notifies once when a miner crosses threshold_c, and again only after it has
+// cooled below threshold_c minus the hysteresis; replaces any running watchdog
 #[tauri::command]
-async fn restart_miner(ip: String) -> Result<serde_json::Value, String> {
-    let client = reqwest::Client::builder()
+async fn start_temp_watchdog(
+    app: AppHandle,
+    state: State<'_, TempWatchdogState>,
+    ips: Vec<String>,
+    threshold_c: f64,
+    poll_secs: u64,
+) -> Result<(), String> {
+    let _timer = CommandTimer::start("start_temp_watchdog", || {
+        format!("ips={:?} threshold_c={:?} poll_secs={:?}", ips, threshold_c, poll_secs)
+    });
+    let client = http_client_builder()
         .timeout(Duration::from_secs(10))
         .build()
         .map_err(|e| e.to_string())?;
 
-    let url = format!("http://{}/api/system/restart", ip);
+    let interval = Duration::from_secs(poll_secs.max(MIN_MONITOR_INTERVAL_SECS));
 
-    let response = client
-        .post(&url)
-        .send()
-        .await
+    let mut shutdown = shutdown_signal(&app);
+    let handle = tauri::async_runtime::spawn(async move {
+        let mut alerted: HashSet<String> = HashSet::new();
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            if !next_tick(&mut ticker, &mut shutdown).await {
+                break;
+            }
+            if is_paused(&app) {
+                continue;
+            }
+
+            let polls = ips.iter().map(|ip| {
+                let client = &client;
+                let app = &app;
+                async move {
+                    let _permit = app.state::<RequestBudget>().inner().permits.acquire().await.ok();
+                    (ip, fetch_miner_data(app, client, ip, 0, None).await)
+                }
+            });
+
+            for (ip, result) in join_all(polls).await {
+                let Some(temp) = result.ok().and_then(|data| data.get("temp").and_then(|v| v.as_f64())) else {
+                    continue;
+                };
+
+                if temp >= threshold_c && !alerted.contains(ip) {
+                    alerted.insert(ip.clone());
+                    let _ = app
+                        .notification()
+                        .builder()
+                        .title("Miner overheating")
+                        .body(format!("{} is at {:.1}°C (threshold {:.1}°C)", ip, temp, threshold_c))
+                        .show();
+                } else if temp < threshold_c - TEMP_ALERT_HYSTERESIS_C {
+                    alerted.remove(ip);
+                }
+            }
+        }
+    });
+
+    // Swap in the new task and stop the previous one
+    let previous = state.task.lock().map_err(|e| e.to_string())?.replace(handle);
+    if let Some(previous) = previous {
+        previous.abort();
+    }
+
+    Ok(())
+}
+
+// Command to stop the temperature watchdog
+#[tauri::command]
+async fn stop_temp_watchdog(state: State<'_, TempWatchdogState>) -> Result<(), String> {
+    let _timer = CommandTimer::start("stop_temp_watchdog", String::new);
+    if let Some(handle) = state.task.lock().map_err(|e| e.to_string())?.take() {
+        handle.abort();
+    }
+    Ok(())
+}
+
+// Command to start the stall watchdog
+// Restarts a miner that still answers but has reported ~0 hashrate for zero_hash_secs,
+// emitting "stall-restart"; each miner is restarted at most once per cooldown
+#[tauri::command]
+async fn start_stall_watchdog(
+    app: AppHandle,
+    state: State<'_, StallWatchdogState>,
+    ips: Vec<String>,
+    zero_hash_secs: u64,
+) -> Result<(), String> {
+    let _timer = CommandTimer::start("start_stall_watchdog", || {
+        format!("ips={:?} zero_hash_secs={:?}", ips, zero_hash_secs)
+    });
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()
         .map_err(|e| e.to_string())?;
 
-    if response.status().is_success() {
-        match response.json::<serde_json::Value>().await {
-            Ok(json) => Ok(json),
-            Err(_) => Ok(serde_json::json!({"success": true})),
+    let threshold = Duration::from_secs(zero_hash_secs);
+
+    let mut shutdown = shutdown_signal(&app);
+    let handle = tauri::async_runtime::spawn(async move {
+        let mut zero_since: HashMap<String, Instant> = HashMap::new();
+        let mut last_restart: HashMap<String, Instant> = HashMap::new();
+        let mut ticker = tokio::time::interval(Duration::from_secs(STALL_POLL_SECS));
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            if !next_tick(&mut ticker, &mut shutdown).await {
+                break;
+            }
+            if is_paused(&app) {
+                continue;
+            }
+
+            let polls = ips.iter().map(|ip| {
+                let client = &client;
+                let app = &app;
+                async move {
+                    let _permit = app.state::<RequestBudget>().inner().permits.acquire().await.ok();
+                    (ip, fetch_miner_data(app, client, ip, 0, None).await)
+                }
+            });
+
+            for (ip, result) in join_all(polls).await {
+                // Only a miner that still answers counts as stalled
+                let Some(hashrate) = result.ok().and_then(|data| normalize_miner_json(&data).hash_rate) else {
+                    zero_since.remove(ip);
+                    continue;
+                };
+                if hashrate > STALL_HASHRATE_EPSILON_GHS {
+                    zero_since.remove(ip);
+                    continue;
+                }
+
+                let stalled_for = zero_since.entry(ip.clone()).or_insert_with(Instant::now).elapsed();
+                let cooling_down = last_restart.get(ip).is_some_and(|at| at.elapsed() < STALL_RESTART_COOLDOWN);
+                if stalled_for < threshold || cooling_down {
+                    continue;
+                }
+
+                let ok = restart_miner(app.clone(), ip.clone(), None).await.is_ok();
+                last_restart.insert(ip.clone(), Instant::now());
+                zero_since.remove(ip);
+                let _ = app.emit("stall-restart", StallRestartEvent {
+                    ip: ip.clone(),
+                    stalled_secs: stalled_for.as_secs(),
+                    ok,
+                });
+            }
         }
-    } else {
-        Err(format!("Miner restart failed with status: {}", response.status()))
+    });
+
+    // Swap in the new task and stop the previous one
+    let previous = state.task.lock().map_err(|e| e.to_string())?.replace(handle);
+    if let Some(previous) = previous {
+        previous.abort();
     }
+
+    Ok(())
 }
 
-// Command to open analytics window
+// Command to stop the stall watchdog
 #[tauri::command]
-async fn open_analytics_window(app: AppHandle) -> Result<(), String> {
-    // Check if window already exists
-    if let Some(window) = app.get_webview_window("analytics") {
-        // Window exists, focus it
-        window.set_focus().map_err(|e| e.to_string())?;
-        return Ok(());
+async fn stop_stall_watchdog(state: State<'_, StallWatchdogState>) -> Result<(), String> {
+    let _timer = CommandTimer::start("stop_stall_watchdog", String::new);
+    if let Some(handle) = state.task.lock().map_err(|e| e.to_string())?.take() {
+        handle.abort();
     }
+    Ok(())
+}
 
-    // Create new analytics window
-    let url = WebviewUrl::App("analytics".into());
+// Read every scheduled settings change from the store
+fn load_scheduled(app: &AppHandle) -> Result<Vec<ScheduledSettings>, String> {
+    let store = app.store(SCHEDULE_STORE_FILE).map_err(|e| e.to_string())?;
+    let mut scheduled: Vec<ScheduledSettings> = store
+        .values()
+        .into_iter()
+        .filter_map(|value| serde_json::from_value(value).ok())
+        .collect();
+    scheduled.sort_by_key(|change| (change.at_unix, change.id));
+    Ok(scheduled)
+}
+
+// Remove and return the scheduled changes whose time has come
+fn take_due_scheduled(app: &AppHandle, now: i64) -> Result<Vec<ScheduledSettings>, String> {
+    let due: Vec<ScheduledSettings> = load_scheduled(app)?
+        .into_iter()
+        .filter(|change| change.at_unix <= now)
+        .collect();
+    if !due.is_empty() {
+        let store = app.store(SCHEDULE_STORE_FILE).map_err(|e| e.to_string())?;
+        for change in &due {
+            store.delete(change.id.to_string());
+        }
+        store.save().map_err(|e| e.to_string())?;
+    }
+    Ok(due)
+}
+
+// Whether background activity is paused with set_paused
+fn is_paused(app: &AppHandle) -> bool {
+    app.state::<PauseState>().paused.load(Ordering::SeqCst)
+}
+
+// Command to pause or resume all background polling (monitor, watchdogs, thermal protection, scheduler)
+// Paused loops skip their rounds and pick up again on the next interval after resuming;
+// scheduled changes that fall due while paused are applied on resume. Emits "paused-changed"
+#[tauri::command]
+async fn set_paused(app: AppHandle, state: State<'_, PauseState>, paused: bool) -> Result<(), String> {
+    let _timer = CommandTimer::start("set_paused", || format!("paused={:?}", paused));
+    state.paused.store(paused, Ordering::SeqCst);
+    app.emit("paused-changed", paused).map_err(|e| e.to_string())
+}
+
+// Start the background task that applies scheduled settings changes when they fall due
+// Each change is applied once (safe-range checks included) and reported via "scheduled-settings-applied"
+fn start_settings_scheduler(app: &AppHandle) {
+    let app_handle = app.clone();
+    let mut shutdown = shutdown_signal(app);
+    let handle = tauri::async_runtime::spawn(async move {
+        let app = app_handle;
+        let mut ticker = tokio::time::interval(Duration::from_secs(SCHEDULER_POLL_SECS));
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            if !next_tick(&mut ticker, &mut shutdown).await {
+                break;
+            }
+            if is_paused(&app) {
+                continue;
+            }
+
+            let due = match take_due_scheduled(&app, unix_now()) {
+                Ok(due) => due,
+                Err(e) => {
+                    log::warn!("Failed to read scheduled settings: {}", e);
+                    continue;
+                }
+            };
+
+            let applies = due.into_iter().map(|change| {
+                let app = app.clone();
+                async move {
+                    let result = update_miner_settings(
+                        app,
+                        change.ip.clone(),
+                        change.frequency,
+                        change.core_voltage,
+                        None,
+                        None,
+                        None,
+                        None,
+                    )
+                    .await;
+                    ScheduledSettingsApplied {
+                        id: change.id,
+                        ip: change.ip,
+                        ok: result.is_ok(),
+                        error: result.err(),
+                    }
+                }
+            });
+            for applied in join_all(applies).await {
+                let _ = app.emit("scheduled-settings-applied", applied);
+            }
+        }
+    });
+
+    if let Ok(mut task) = app.state::<SchedulerState>().task.lock() {
+        if let Some(previous) = task.replace(handle) {
+            previous.abort();
+        }
+    }
+}
+
+// Command to queue a frequency/voltage change for a miner at a unix time
+// Times in the past are applied on the scheduler's next tick; returns the schedule id
+#[tauri::command]
+async fn schedule_settings(
+    app: AppHandle,
+    ip: String,
+    at_unix: i64,
+    frequency: u32,
+    core_voltage: u32,
+) -> Result<u64, MinerError> {
+    let _timer = CommandTimer::start("schedule_settings", || {
+        format!(
+            "ip={:?} at_unix={:?} frequency={:?} core_voltage={:?}",
+            ip, at_unix, frequency, core_voltage
+        )
+    });
+    validate_miner_target(&ip)?;
+
+    let id = load_scheduled(&app)
+        .map_err(MinerError::Internal)?
+        .iter()
+        .map(|change| change.id)
+        .max()
+        .map_or(1, |id| id + 1);
+    let change = ScheduledSettings {
+        id,
+        ip,
+        at_unix,
+        frequency,
+        core_voltage,
+    };
+
+    let store = app.store(SCHEDULE_STORE_FILE).map_err(|e| MinerError::Internal(e.to_string()))?;
+    store.set(id.to_string(), serde_json::to_value(&change).map_err(|e| MinerError::Internal(e.to_string()))?);
+    store.save().map_err(|e| MinerError::Internal(e.to_string()))?;
+    Ok(id)
+}
+
+// Command to list pending scheduled settings changes, soonest first
+#[tauri::command]
+async fn list_scheduled(app: AppHandle) -> Result<Vec<ScheduledSettings>, String> {
+    let _timer = CommandTimer::start("list_scheduled", String::new);
+    load_scheduled(&app)
+}
+
+// Command to cancel a pending scheduled settings change; returns false if it no longer exists
+#[tauri::command]
+async fn cancel_scheduled(app: AppHandle, id: u64) -> Result<bool, String> {
+    let _timer = CommandTimer::start("cancel_scheduled", || format!("id={:?}", id));
+    let store = app.store(SCHEDULE_STORE_FILE).map_err(|e| e.to_string())?;
+    let removed = store.delete(id.to_string());
+    store.save().map_err(|e| e.to_string())?;
+    Ok(removed)
+}
+
+// Command to enable thermal protection for a miner
+// A background poll drops the miner to the safe frequency/voltage when temp exceeds
+// max_temp_c and emits "thermal-throttle"; restore_settings puts the originals back.
+// The safe settings are checked against the miner's ASIC model up front
+#[tauri::command]
+async fn set_thermal_protection(
+    app: AppHandle,
+    state: State<'_, ThermalProtectionState>,
+    ip: String,
+    max_temp_c: f64,
+    safe_frequency: u32,
+    safe_voltage: u32,
+) -> Result<(), String> {
+    let _timer = CommandTimer::start("set_thermal_protection", || {
+        format!(
+            "ip={:?} max_temp_c={:?} safe_frequency={:?} safe_voltage={:?}",
+            ip, max_temp_c, safe_frequency, safe_voltage
+        )
+    });
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let info = fetch_miner_data(&app, &client, &ip, DEFAULT_FETCH_RETRIES, None)
+        .await
+        .map_err(|e| e.to_string())?;
+    let model = info
+        .get("ASICModel")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| format!("Could not determine ASIC model for {}", ip))?;
+    validate_settings_for_model(&model, safe_frequency, safe_voltage).map_err(|e| e.to_string())?;
+
+    let task_ip = ip.clone();
+    let mut shutdown = shutdown_signal(&app);
+    let handle = tauri::async_runtime::spawn(async move {
+        let ip = task_ip;
+        let mut ticker = tokio::time::interval(Duration::from_secs(THERMAL_POLL_SECS));
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            if !next_tick(&mut ticker, &mut shutdown).await {
+                break;
+            }
+            if is_paused(&app) {
+                continue;
+            }
+
+            let permit = app.state::<RequestBudget>().inner().permits.acquire().await.ok();
+            let result = fetch_miner_data(&app, &client, &ip, 0, None).await;
+            drop(permit);
+            let Ok(data) = result else {
+                continue;
+            };
+            let Some(temp) = data.get("temp").and_then(|v| v.as_f64()) else {
+                continue;
+            };
+            if temp <= max_temp_c {
+                continue;
+            }
+
+            // Already throttled; wait for restore_settings
+            let throttled = app
+                .state::<ThermalProtectionState>()
+                .saved
+                .lock()
+                .map_or(true, |saved| saved.contains_key(&ip));
+            if throttled {
+                continue;
+            }
+
+            let number = |key: &str| data.get(key).and_then(|v| v.as_f64()).map(|v| v.round() as u32);
+            let original = match (number("frequency"), number("coreVoltage")) {
+                (Some(frequency), Some(core_voltage)) => Some(MinerSettingsUpdate { frequency, core_voltage }),
+                _ => None,
+            };
+
+            // Same path as a manual change, so the throttle is validated and logged
+            let result = update_miner_settings(
+                app.clone(),
+                ip.clone(),
+                safe_frequency,
+                safe_voltage,
+                Some(model.clone()),
+                None,
+                None,
+                None,
+            )
+            .await;
+            match result {
+                Ok(_) => {
+                    if let Ok(mut saved) = app.state::<ThermalProtectionState>().saved.lock() {
+                        saved.insert(ip.clone(), original);
+                    }
+                    let _ = app.emit("thermal-throttle", ThermalThrottleEvent {
+                        ip: ip.clone(),
+                        temp,
+                        frequency: safe_frequency,
+                        core_voltage: safe_voltage,
+                    });
+                }
+                Err(e) => log::error!("Failed to throttle {}: {}", ip, e),
+            }
+        }
+    });
 
-    WebviewWindowBuilder::new(&app, "analytics", url)
-        .title("Mining Analytics - AxeOS Live!")
-        .inner_size(1200.0, 800.0)
-        .min_inner_size(800.0, 600.0)
-        .resizable(true)
-        .center()
-        .build()
-        .map_err(|e| e.to_string())?;
+    // Replace any existing protection task for this miner
+    let previous = state.tasks.lock().map_err(|e| e.to_string())?.insert(ip, handle);
+    if let Some(previous) = previous {
+        previous.abort();
+    }
 
     Ok(())
 }
 
-// Command to close analytics window
+// Command to disable thermal protection for a miner
+// Also forgets its pre-throttle settings, so a later protection run saves fresh ones
 #[tauri::command]
-async fn close_analytics_window(app: AppHandle) -> Result<(), String> {
-    if let Some(window) = app.get_webview_window("analytics") {
-        window.close().map_err(|e| e.to_string())?;
+async fn clear_thermal_protection(state: State<'_, ThermalProtectionState>, ip: String) -> Result<(), String> {
+    let _timer = CommandTimer::start("clear_thermal_protection", || format!("ip={:?}", ip));
+    if let Some(handle) = state.tasks.lock().map_err(|e| e.to_string())?.remove(&ip) {
+        handle.abort();
     }
+    state.saved.lock().map_err(|e| e.to_string())?.remove(&ip);
     Ok(())
 }
 
-// Command to open settings window
+// Command to restore the settings a miner had before it was thermally throttled
 #[tauri::command]
-async fn open_settings_window(app: AppHandle) -> Result<(), String> {
-    // Check if window already exists
-    if let Some(window) = app.get_webview_window("settings") {
-        // Window exists, focus it
-        window.set_focus().map_err(|e| e.to_string())?;
-        return Ok(());
+async fn restore_settings(
+    app: AppHandle,
+    state: State<'_, ThermalProtectionState>,
+    ip: String,
+) -> Result<serde_json::Value, MinerError> {
+    let _timer = CommandTimer::start("restore_settings", || format!("ip={:?}", ip));
+    let original = state
+        .saved
+        .lock()
+        .map_err(|e| MinerError::Internal(e.to_string()))?
+        .remove(&ip)
+        .ok_or_else(|| MinerError::InvalidInput(format!("No saved settings for {}", ip)))?
+        .ok_or_else(|| {
+            MinerError::Unsupported(format!("The settings of {} could not be read before it was throttled", ip))
+        })?;
+
+    // These are the settings the miner ran before, so skip the safe range check
+    update_miner_settings(app, ip, original.frequency, original.core_voltage, None, Some(true), None, None).await
+}
+
+// Sleep for `secs`, waking every second to check the cancel flag
+// Returns false if cancelled while waiting
+async fn dwell(secs: u64, cancelled: &AtomicBool) -> bool {
+    for _ in 0..secs {
+        if cancelled.load(Ordering::SeqCst) {
+            return false;
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
     }
+    !cancelled.load(Ordering::SeqCst)
+}
 
-    // Create new settings window
-    let url = WebviewUrl::App("settings".into());
+// Read hashrate/power/temp from the miner after a setting has settled
+async fn measure_miner(app: &AppHandle, client: &reqwest::Client, ip: &str) -> Result<TuneMeasurement, MinerError> {
+    let data = fetch_miner_data(app, client, ip, DEFAULT_FETCH_RETRIES, None).await?;
+    let number = |key: &str| data.get(key).and_then(|v| v.as_f64());
 
-    WebviewWindowBuilder::new(&app, "settings", url)
-        .title("Settings - AxeOS Live!")
-        .inner_size(500.0, 700.0)
-        .min_inner_size(400.0, 500.0)
-        .resizable(true)
-        .center()
-        .build()
-        .map_err(|e| e.to_string())?;
+    let hashrate = number("hashRate").unwrap_or(0.0);
+    let power = number("power").unwrap_or(0.0);
+    // hashRate is GH/s, so W / (GH/s / 1000) gives J/TH
+    let efficiency_j_th = (hashrate > 0.0).then(|| power / (hashrate / 1000.0));
 
-    Ok(())
+    Ok(TuneMeasurement {
+        hashrate,
+        power,
+        temp: number("temp"),
+        efficiency_j_th,
+    })
 }
 
-// Command to close settings window
+// Command to sweep frequency/voltage combinations and find the most efficient one
+// Each candidate is applied, left to settle for dwell_secs, then measured; the best J/TH
+// under max_temp_c wins and is applied. Cancelling restores the original settings
 #[tauri::command]
-async fn close_settings_window(app: AppHandle) -> Result<(), String> {
-    if let Some(window) = app.get_webview_window("settings") {
-        window.close().map_err(|e| e.to_string())?;
+#[allow(clippy::too_many_arguments)]
+async fn autotune(
+    app: AppHandle,
+    state: State<'_, AutotuneState>,
+    ip: String,
+    freq_range: (u32, u32),
+    voltage_range: (u32, u32),
+    step: u32,
+    dwell_secs: u64,
+    max_temp_c: Option<f64>,
+) -> Result<AutotuneResult, MinerError> {
+    let _timer = CommandTimer::start("autotune", || {
+        format!(
+            "ip={:?} freq_range={:?} voltage_range={:?} step={:?} dwell_secs={:?} max_temp_c={:?}",
+            ip, freq_range, voltage_range, step, dwell_secs, max_temp_c
+        )
+    });
+    if step == 0 || freq_range.0 > freq_range.1 || voltage_range.0 > voltage_range.1 {
+        return Err(MinerError::InvalidInput("Invalid autotune range or step".to_string()));
     }
-    Ok(())
-}
+    let ceiling = max_temp_c.unwrap_or(AUTOTUNE_DEFAULT_MAX_TEMP_C);
 
-// Command to open tools window
-#[tauri::command]
-async fn open_tools_window(app: AppHandle) -> Result<(), String> {
-    // Check if window already exists
-    if let Some(window) = app.get_webview_window("tools") {
-        // Window exists, focus it
-        window.set_focus().map_err(|e| e.to_string())?;
-        return Ok(());
-    }
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
 
-    // Create new tools window
-    let url = WebviewUrl::App("tools".into());
+    // Remember where we started so we can put it back
+    let original = fetch_miner_data(&app, &client, &ip, DEFAULT_FETCH_RETRIES, None).await?;
+    let original_number = |key: &str| original.get(key).and_then(|v| v.as_f64()).map(|v| v.round() as u32);
+    let (Some(original_frequency), Some(original_voltage)) = (original_number("frequency"), original_number("coreVoltage")) else {
+        return Err(MinerError::ParseError);
+    };
+    let model = original.get("ASICModel").and_then(|v| v.as_str()).map(|s| s.to_string());
 
-    WebviewWindowBuilder::new(&app, "tools", url)
-        .title("Tools & Settings - AxeOS Live!")
-        .inner_size(500.0, 750.0)
-        .min_inner_size(450.0, 600.0)
-        .resizable(true)
-        .center()
-        .build()
-        .map_err(|e| e.to_string())?;
+    let candidates: Vec<(u32, u32)> = (freq_range.0..=freq_range.1)
+        .step_by(step as usize)
+        .flat_map(|f| {
+            (voltage_range.0..=voltage_range.1)
+                .step_by(AUTOTUNE_VOLTAGE_STEP_MV as usize)
+                .map(move |v| (f, v))
+        })
+        .collect();
 
-    Ok(())
-}
+    let cancelled = &state.cancelled;
+    cancelled.store(false, Ordering::SeqCst);
 
-// Command to open benchmark window with larger size
-// Optional miner_ip parameter to pre-select a miner
-#[tauri::command]
-async fn open_benchmark_window(app: AppHandle, miner_ip: Option<String>) -> Result<(), String> {
-    // Check if window already exists
-    if let Some(window) = app.get_webview_window("benchmark") {
-        // Window exists, focus it and emit event to select miner
-        window.set_focus().map_err(|e| e.to_string())?;
-        if let Some(ip) = miner_ip {
-            window.emit("select-miner", ip).map_err(|e| e.to_string())?;
+    let sweep = async {
+        let mut best: Option<AutotuneResult> = None;
+        for (index, (frequency, core_voltage)) in candidates.iter().copied().enumerate() {
+            update_miner_settings(app.clone(), ip.clone(), frequency, core_voltage, model.clone(), None, None, None).await?;
+
+            if !dwell(dwell_secs, cancelled).await {
+                return Err(MinerError::Cancelled);
+            }
+
+            let measurement = measure_miner(&app, &client, &ip).await?;
+            let _ = app.emit("autotune-progress", AutotuneProgress {
+                step: index + 1,
+                total: candidates.len(),
+                frequency,
+                core_voltage,
+                measurement: measurement.clone(),
+            });
+
+            let within_ceiling = measurement.temp.is_some_and(|t| t <= ceiling);
+            let Some(efficiency) = measurement.efficiency_j_th.filter(|_| within_ceiling) else {
+                continue;
+            };
+            if best.as_ref().map_or(true, |b| efficiency < b.measurement.efficiency_j_th.unwrap_or(f64::MAX)) {
+                best = Some(AutotuneResult {
+                    frequency,
+                    core_voltage,
+                    measurement,
+                });
+            }
         }
-        return Ok(());
-    }
+        Ok(best)
+    };
+    let outcome = sweep.await;
+    cancelled.store(false, Ordering::SeqCst);
 
-    // Create new benchmark window with larger dimensions
-    // Append miner_ip as query parameter if provided
-    let url = match &miner_ip {
-        Some(ip) => WebviewUrl::App(format!("benchmark?miner={}", ip).into()),
-        None => WebviewUrl::App("benchmark".into()),
-    };
-
-    WebviewWindowBuilder::new(&app, "benchmark", url)
-        .title("Hashrate Benchmark - AxeOS Live!")
-        .inner_size(900.0, 800.0)
-        .min_inner_size(700.0, 600.0)
-        .resizable(true)
-        .center()
-        .build()
-        .map_err(|e| e.to_string())?;
+    // Leave the miner on the winner, or back where it started
+    let (frequency, core_voltage) = match &outcome {
+        Ok(Some(winner)) => (winner.frequency, winner.core_voltage),
+        _ => (original_frequency, original_voltage),
+    };
+    patch_miner_system(&app, &ip, &MinerSettingsUpdate { frequency, core_voltage }, None).await?;
+
+    outcome?.ok_or_else(|| {
+        MinerError::Unsupported(format!("No setting stayed under {:.1}°C, original settings restored", ceiling))
+    })
+}
 
+// Command to cancel a running autotune
+#[tauri::command]
+async fn cancel_autotune(state: State<'_, AutotuneState>) -> Result<(), String> {
+    let _timer = CommandTimer::start("cancel_autotune", String::new);
+    state.cancelled.store(true, Ordering::SeqCst);
     Ok(())
 }
 
-// Command to update miner settings
+// Command to run a benchmark in the backend so it survives the benchmark window closing
+// Each step is applied, sampled every few seconds for its dwell period ("benchmark-sample"
+// events), averaged and saved to the database. Original settings are restored at the end
 #[tauri::command]
-async fn update_miner_settings(ip: String, frequency: u32, core_voltage: u32) -> Result<serde_json::Value, String> {
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(10))
-        .build()
-        .map_err(|e| e.to_string())?;
+async fn run_benchmark(
+    app: AppHandle,
+    state: State<'_, BenchmarkState>,
+    db: State<'_, Database>,
+    ip: String,
+    steps: Vec<BenchStep>,
+) -> Result<BenchmarkSummary, MinerError> {
+    let _timer = CommandTimer::start("run_benchmark", || format!("ip={:?} steps={}", ip, steps.len()));
+    if steps.is_empty() {
+        return Err(MinerError::InvalidInput("Benchmark needs at least one step".to_string()));
+    }
 
-    let url = format!("http://{}/api/system", ip);
+    let client = http_client_builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
 
-    let settings = MinerSettingsUpdate {
-        frequency,
-        core_voltage,
+    let original = fetch_miner_data(&app, &client, &ip, DEFAULT_FETCH_RETRIES, None).await?;
+    let original_number = |key: &str| original.get(key).and_then(|v| v.as_f64()).map(|v| v.round() as u32);
+    let (Some(original_frequency), Some(original_voltage)) = (original_number("frequency"), original_number("coreVoltage")) else {
+        return Err(MinerError::ParseError);
     };
+    let model = original.get("ASICModel").and_then(|v| v.as_str()).map(|s| s.to_string());
 
-    let response = client
-        .patch(&url)
-        .json(&settings)
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    let cancelled = &state.cancelled;
+    cancelled.store(false, Ordering::SeqCst);
 
-    if response.status().is_success() {
-        match response.json::<serde_json::Value>().await {
-            Ok(json) => Ok(json),
-            Err(_) => Ok(serde_json::json!({"success": true})),
-        }
-    } else {
-        let status = response.status();
-        let error_text = response.text().await.unwrap_or_default();
-        Err(format!("Failed to update settings ({}): {}", status, error_text))
-    }
-}
+    let mut results: Vec<BenchStepResult> = Vec::new();
+    let mut was_cancelled = false;
 
-// Check if a single IP has a miner
-async fn check_miner_at_ip(client: &reqwest::Client, ip: String) -> Option<DiscoveredMiner> {
-    let api_paths = vec![
-        "/api/system/info",
-        "/api/system",
-    ];
+    let restore_original = MinerSettingsUpdate {
+        frequency: original_frequency,
+        core_voltage: original_voltage,
+    };
 
-    for path in &api_paths {
-        let url = format!("http://{}{}", ip, path);
-        match client.get(&url).send().await {
-            Ok(response) => {
-                if response.status().is_success() {
-                    if let Ok(json) = response.json::<serde_json::Value>().await {
-                        // Extract miner info from response
-                        let hostname = json.get("hostname")
-                            .and_then(|v| v.as_str())
-                            .map(|s| s.to_string());
-                        let version = json.get("version")
-                            .or_else(|| json.get("axeOSVersion"))
-                            .and_then(|v| v.as_str())
-                            .map(|s| s.to_string());
-                        let model = json.get("ASICModel")
-                            .and_then(|v| v.as_str())
-                            .map(|s| s.to_string());
+    'steps: for (index, step) in steps.iter().enumerate() {
+        if let Err(e) = update_miner_settings(app.clone(), ip.clone(), step.frequency, step.core_voltage, model.clone(), None, None, None).await {
+            cancelled.store(false, Ordering::SeqCst);
+            let _ = patch_miner_system(&app, &ip, &restore_original, None).await;
+            return Err(e);
+        }
 
-                        return Some(DiscoveredMiner {
-                            ip,
-                            hostname,
-                            version,
-                            model,
-                        });
-                    }
-                }
+        let mut measurements = Vec::new();
+        let mut elapsed = 0;
+        while elapsed < step.dwell_secs {
+            let wait = BENCHMARK_SAMPLE_SECS.min(step.dwell_secs - elapsed);
+            if !dwell(wait, cancelled).await {
+                was_cancelled = true;
+                break 'steps;
+            }
+            elapsed += wait;
+
+            if let Ok(measurement) = measure_miner(&app, &client, &ip).await {
+                let _ = app.emit("benchmark-sample", BenchmarkSample {
+                    ip: ip.clone(),
+                    step: index,
+                    elapsed_secs: elapsed,
+                    measurement: measurement.clone(),
+                });
+                measurements.push(measurement);
             }
-            Err(_) => continue,
         }
-    }
-    None
-}
 
-// Command to scan network for miners
-#[tauri::command]
-async fn scan_network(subnet: String, start: u8, end: u8) -> Result<Vec<DiscoveredMiner>, String> {
-    // Parse the subnet (e.g., "192.168.1")
-    let parts: Vec<&str> = subnet.split('.').collect();
-    if parts.len() != 3 {
-        return Err("Invalid subnet format. Expected format: 192.168.1".to_string());
-    }
+        if measurements.is_empty() {
+            continue;
+        }
+        let count = measurements.len() as f64;
+        let avg_hashrate = measurements.iter().map(|m| m.hashrate).sum::<f64>() / count;
+        let avg_power = measurements.iter().map(|m| m.power).sum::<f64>() / count;
+        let temps: Vec<f64> = measurements.iter().filter_map(|m| m.temp).collect();
+        let avg_temp = (!temps.is_empty()).then(|| temps.iter().sum::<f64>() / temps.len() as f64);
+        let efficiency_j_th = (avg_hashrate > 0.0).then(|| avg_power / (avg_hashrate / 1000.0));
 
-    // Validate subnet parts
-    for part in &parts {
-        if part.parse::<u8>().is_err() {
-            return Err(format!("Invalid subnet octet: {}", part));
+        let result = BenchStepResult {
+            frequency: step.frequency,
+            core_voltage: step.core_voltage,
+            avg_hashrate,
+            avg_power,
+            avg_temp,
+            efficiency_j_th,
+        };
+
+        // Without a database the results are still returned, just not kept
+        let Ok(pool) = db.pool() else {
+            results.push(result);
+            continue;
+        };
+        let saved = sqlx::query(
+            "INSERT INTO benchmark_results
+               (ip, timestamp, frequency, core_voltage, avg_hashrate, avg_power, avg_temp, efficiency_j_th)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&ip)
+        .bind(unix_now())
+        .bind(result.frequency)
+        .bind(result.core_voltage)
+        .bind(result.avg_hashrate)
+        .bind(result.avg_power)
+        .bind(result.avg_temp)
+        .bind(result.efficiency_j_th)
+        .execute(pool)
+        .await;
+        if let Err(e) = saved {
+            log::error!("Failed to save benchmark result for {}: {}", ip, e);
         }
+
+        results.push(result);
     }
 
-    // Create a client with short timeout for scanning
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_millis(1500)) // Short timeout for scanning
-        .build()
-        .map_err(|e| e.to_string())?;
+    cancelled.store(false, Ordering::SeqCst);
+    patch_miner_system(&app, &ip, &restore_original, None).await?;
+
+    let best_hashrate = results
+        .iter()
+        .max_by(|a, b| a.avg_hashrate.total_cmp(&b.avg_hashrate))
+        .cloned();
+    let best_efficiency = results
+        .iter()
+        .filter(|r| r.efficiency_j_th.is_some())
+        .min_by(|a, b| a.efficiency_j_th.unwrap_or(f64::MAX).total_cmp(&b.efficiency_j_th.unwrap_or(f64::MAX)))
+        .cloned();
 
-    // Create tasks for all IPs in range
-    let mut tasks = Vec::new();
+    Ok(BenchmarkSummary {
+        ip,
+        cancelled: was_cancelled,
+        results,
+        best_hashrate,
+        best_efficiency,
+    })
+}
 
-    for i in start..=end {
-        let ip = format!("{}.{}", subnet, i);
-        let client_clone = client.clone();
+// Command to cancel a running benchmark (the miner's original settings are restored)
+#[tauri::command]
+async fn cancel_benchmark(state: State<'_, BenchmarkState>) -> Result<(), String> {
+    let _timer = CommandTimer::start("cancel_benchmark", String::new);
+    state.cancelled.store(true, Ordering::SeqCst);
+    Ok(())
+}
 
-        tasks.push(async move {
-            check_miner_at_ip(&client_clone, ip).await
-        });
-    }
+// Command to subscribe to a miner's websocket stream
+// Each frame is re-emitted as "miner-ws"; "miner-ws-closed" fires when the stream ends.
+// Returns an error if the firmware has no websocket so the UI can keep polling
+#[tauri::command]
+async fn subscribe_ws(app: AppHandle, state: State<'_, WsState>, ip: String) -> Result<(), MinerError> {
+    let _timer = CommandTimer::start("subscribe_ws", || format!("ip={:?}", ip));
+    let url = format!("ws://{}{}", url_host(&connect_host(&ip).await), MINER_WS_PATH);
+    let (stream, _) = tokio::time::timeout(Duration::from_secs(5), tokio_tungstenite::connect_async(url.as_str()))
+        .await
+        .map_err(|_| MinerError::Timeout)?
+        .map_err(|e| MinerError::Unsupported(format!("Websocket not available at {}: {}", url, e)))?;
 
-    // Run all scans concurrently
-    let results = join_all(tasks).await;
+    let task_ip = ip.clone();
+    let mut shutdown = shutdown_signal(&app);
+    let handle = tauri::async_runtime::spawn(async move {
+        let (_, mut read) = stream.split();
+        loop {
+            let message = tokio::select! {
+                message = read.next() => message,
+                _ = shutdown.wait_for(|stopping| *stopping) => return,
+            };
+            let Some(Ok(message)) = message else {
+                break;
+            };
+            let data = match message {
+                WsMessage::Text(text) => serde_json::from_str(text.as_str())
+                    .unwrap_or_else(|_| serde_json::Value::from(text.as_str())),
+                WsMessage::Close(_) => break,
+                _ => continue,
+            };
+            let _ = app.emit("miner-ws", MinerDataResult::new(task_ip.clone(), Ok(data)));
+        }
+        let _ = app.emit("miner-ws-closed", &task_ip);
+    });
 
-    // Collect found miners
-    let miners: Vec<DiscoveredMiner> = results.into_iter().flatten().collect();
+    // Replace any existing subscription for this miner
+    let previous = state.connections.lock().map_err(|e| MinerError::Internal(e.to_string()))?.insert(ip, handle);
+    if let Some(previous) = previous {
+        previous.abort();
+    }
 
-    Ok(miners)
+    Ok(())
+}
+
+// Command to close a miner's websocket subscription
+#[tauri::command]
+async fn unsubscribe_ws(state: State<'_, WsState>, ip: String) -> Result<(), String> {
+    let _timer = CommandTimer::start("unsubscribe_ws", || format!("ip={:?}", ip));
+    if let Some(handle) = state.connections.lock().map_err(|e| e.to_string())?.remove(&ip) {
+        handle.abort();
+    }
+    Ok(())
 }
 
 // Command to get local network info (for auto-detecting subnet)
 #[tauri::command]
 async fn get_local_subnet() -> Result<String, String> {
+    let _timer = CommandTimer::start("get_local_subnet", String::new);
     // Try to get local IP addresses
     let interfaces = local_ip_address::list_afinet_netifas();
 
@@ -350,9 +5954,69 @@ async fn get_local_subnet() -> Result<String, String> {
     }
 }
 
+// Read typed app settings from the store, falling back to defaults
+fn load_app_settings(app: &AppHandle) -> AppSettings {
+    app.store(APP_SETTINGS_STORE_FILE)
+        .ok()
+        .and_then(|store| store.get(APP_SETTINGS_KEY))
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+// Command to get the app settings
+#[tauri::command]
+async fn get_app_settings(app: AppHandle) -> Result<AppSettings, String> {
+    let _timer = CommandTimer::start("get_app_settings", String::new);
+    Ok(load_app_settings(&app))
+}
+
+// Command to save the app settings
+#[tauri::command]
+async fn set_app_settings(app: AppHandle, settings: AppSettings) -> Result<(), String> {
+    let _timer = CommandTimer::start("set_app_settings", String::new);
+    validate_scan_timeout(settings.scan_timeout_ms)?;
+    if let Some(user_agent) = &settings.user_agent {
+        if reqwest::header::HeaderValue::from_str(user_agent.trim()).is_err() {
+            return Err(format!("Invalid User-Agent: '{}'", user_agent));
+        }
+    }
+    if let Some(path) = settings.api_paths.iter().find(|path| path.trim().is_empty() || path.contains("://")) {
+        return Err(format!("Invalid API path: '{}'. Expected a path like /api/system/info", path));
+    }
+    if settings.poll_interval_secs < MIN_MONITOR_INTERVAL_SECS {
+        return Err(format!(
+            "Invalid poll interval: {}s. Must be at least {}s",
+            settings.poll_interval_secs, MIN_MONITOR_INTERVAL_SECS
+        ));
+    }
+    if let Some(price) = settings.price_per_kwh.filter(|price| !price.is_finite() || *price < 0.0) {
+        return Err(format!("Invalid electricity price: {}", price));
+    }
+
+    let store = app.store(APP_SETTINGS_STORE_FILE).map_err(|e| e.to_string())?;
+    let value = serde_json::to_value(&settings).map_err(|e| e.to_string())?;
+    store.set(APP_SETTINGS_KEY, value);
+    store.save().map_err(|e| e.to_string())?;
+    apply_global_settings(&settings);
+    Ok(())
+}
+
+// Command to get the app version and platform, for the About dialog and bug reports
+#[tauri::command]
+async fn get_app_info(app: AppHandle) -> Result<AppInfo, String> {
+    let _timer = CommandTimer::start("get_app_info", String::new);
+    Ok(AppInfo {
+        version: app.package_info().version.to_string(),
+        tauri_version: tauri::VERSION.to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+    })
+}
+
 // Command to show main window (called from tray)
 #[tauri::command]
 async fn show_main_window(app: AppHandle) -> Result<(), String> {
+    let _timer = CommandTimer::start("show_main_window", String::new);
     if let Some(window) = app.get_webview_window("main") {
         window.show().map_err(|e| e.to_string())?;
         window.set_focus().map_err(|e| e.to_string())?;
@@ -363,19 +6027,170 @@ async fn show_main_window(app: AppHandle) -> Result<(), String> {
 // Command to hide main window to tray
 #[tauri::command]
 async fn hide_to_tray(app: AppHandle) -> Result<(), String> {
+    let _timer = CommandTimer::start("hide_to_tray", String::new);
     if let Some(window) = app.get_webview_window("main") {
         window.hide().map_err(|e| e.to_string())?;
     }
     Ok(())
 }
 
+// Command to pin a window above other apps
+#[tauri::command]
+async fn set_always_on_top(app: AppHandle, label: String, enabled: bool) -> Result<(), String> {
+    let _timer = CommandTimer::start("set_always_on_top", || {
+        format!("label={:?} enabled={:?}", label, enabled)
+    });
+    let window = app
+        .get_webview_window(&label)
+        .ok_or_else(|| format!("Unknown window: {}", label))?;
+    window.set_always_on_top(enabled).map_err(|e| e.to_string())
+}
+
+// Whether a window is currently in kiosk mode
+fn is_kiosk(window: &tauri::Window) -> bool {
+    window
+        .app_handle()
+        .state::<KioskState>()
+        .windows
+        .lock()
+        .is_ok_and(|windows| windows.contains_key(window.label()))
+}
+
+// Command to turn kiosk mode (fullscreen, no decorations) on or off for a window
+// Turning it off restores the previous size, position and decorations. While in kiosk,
+// close requests are ignored so a stray keypress can't hide a wall display
+#[tauri::command]
+async fn set_kiosk(app: AppHandle, state: State<'_, KioskState>, label: String, enabled: bool) -> Result<(), String> {
+    let _timer = CommandTimer::start("set_kiosk", || format!("label={:?} enabled={:?}", label, enabled));
+    let window = app
+        .get_webview_window(&label)
+        .ok_or_else(|| format!("Unknown window: {}", label))?;
+
+    if enabled {
+        let restore = KioskRestore {
+            position: window.outer_position().map_err(|e| e.to_string())?,
+            size: window.inner_size().map_err(|e| e.to_string())?,
+            decorated: window.is_decorated().map_err(|e| e.to_string())?,
+        };
+        // Keep the original geometry if kiosk is enabled twice
+        state.windows.lock().map_err(|e| e.to_string())?.entry(label).or_insert(restore);
+
+        window.set_decorations(false).map_err(|e| e.to_string())?;
+        window.set_fullscreen(true).map_err(|e| e.to_string())?;
+    } else {
+        let restore = state.windows.lock().map_err(|e| e.to_string())?.remove(&label);
+
+        window.set_fullscreen(false).map_err(|e| e.to_string())?;
+        if let Some(restore) = restore {
+            window.set_decorations(restore.decorated).map_err(|e| e.to_string())?;
+            window.set_size(restore.size).map_err(|e| e.to_string())?;
+            window.set_position(restore.position).map_err(|e| e.to_string())?;
+        } else {
+            window.set_decorations(true).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+// Command to set the tray icon tooltip text
+#[tauri::command]
+async fn set_tray_tooltip(app: AppHandle, text: String) -> Result<(), String> {
+    let _timer = CommandTimer::start("set_tray_tooltip", || format!("text={:?}", text));
+    app.state::<TrayIcon>()
+        .set_tooltip(Some(text))
+        .map_err(|e| e.to_string())
+}
+
+// Command to set the tray icon status colour ("healthy", "degraded" or "offline")
+#[tauri::command]
+async fn set_tray_status(app: AppHandle, status: TrayStatus) -> Result<(), String> {
+    let _timer = CommandTimer::start("set_tray_status", || format!("status={:?}", status));
+    let icon = tray_status_icon(status)?;
+    app.state::<TrayIcon>()
+        .set_icon(Some(icon))
+        .map_err(|e| e.to_string())
+}
+
 // Command to quit the application
 #[tauri::command]
 async fn quit_app(app: AppHandle) -> Result<(), String> {
+    let _timer = CommandTimer::start("quit_app", String::new);
     app.exit(0);
     Ok(())
 }
 
+// Command to relaunch the app, for settings the frontend can't apply live
+// Stores are flushed first; the exit handler then stops background tasks as on quit
+#[tauri::command]
+async fn restart_app(app: AppHandle) -> Result<(), String> {
+    let _timer = CommandTimer::start("restart_app", String::new);
+    flush_stores(&app);
+    app.restart();
+}
+
+// Write every loaded store to disk
+fn flush_stores(app: &AppHandle) {
+    for file in STORE_FILES {
+        if let Some(store) = app.get_store(file) {
+            if let Err(e) = store.save() {
+                log::error!("Failed to save store {}: {}", file, e);
+            }
+        }
+    }
+}
+
+// Stop background tasks, close the database and flush stores before exiting
+// Loops are signalled to stop and given SHUTDOWN_TIMEOUT to finish their round; stragglers are aborted
+fn shutdown(app: &AppHandle) {
+    let state = app.state::<ShutdownState>();
+    state.signal.send_replace(true);
+
+    let mut handles = Vec::new();
+    if let Ok(mut task) = app.state::<MonitorState>().task.lock() {
+        handles.extend(task.take());
+    }
+    if let Ok(mut task) = app.state::<TempWatchdogState>().task.lock() {
+        handles.extend(task.take());
+    }
+    if let Ok(mut task) = app.state::<StallWatchdogState>().task.lock() {
+        handles.extend(task.take());
+    }
+    if let Ok(mut task) = app.state::<SchedulerState>().task.lock() {
+        handles.extend(task.take());
+    }
+    if let Ok(mut tasks) = app.state::<ThermalProtectionState>().tasks.lock() {
+        handles.extend(tasks.drain().map(|(_, handle)| handle));
+    }
+    if let Ok(mut connections) = app.state::<WsState>().connections.lock() {
+        handles.extend(connections.drain().map(|(_, handle)| handle));
+    }
+
+    let mut writes = state
+        .writes
+        .lock()
+        .map(|mut writes| std::mem::take(&mut *writes))
+        .unwrap_or_default();
+
+    tauri::async_runtime::block_on(async {
+        let drained = async {
+            join_all(handles.iter_mut()).await;
+            while writes.join_next().await.is_some() {}
+        };
+        if tokio::time::timeout(SHUTDOWN_TIMEOUT, drained).await.is_err() {
+            log::warn!("Background tasks did not stop within {:?}", SHUTDOWN_TIMEOUT);
+        }
+        for handle in &handles {
+            handle.abort();
+        }
+        writes.abort_all();
+        if let Some(pool) = app.try_state::<Database>().and_then(|db| db.pool.clone()) {
+            pool.close().await;
+        }
+    });
+
+    flush_stores(app);
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
   tauri::Builder::default()
@@ -383,10 +6198,65 @@ pub fn run() {
     .plugin(tauri_plugin_store::Builder::new().build())
     .plugin(tauri_plugin_shell::init())
     .plugin(tauri_plugin_sql::Builder::default().build())
+    .manage(ScanState::default())
+    .manage(MonitorState::default())
+    .manage(TempWatchdogState::default())
+    .manage(StallWatchdogState::default())
+    .manage(SchedulerState::default())
+    .manage(PauseState::default())
+    .manage(LatencyState::default())
+    .manage(KioskState::default())
+    .manage(ThermalProtectionState::default())
+    .manage(AutotuneState::default())
+    .manage(BenchmarkState::default())
+    .manage(WsState::default())
+    .manage(ShutdownState::default())
+    .manage(EventState::default())
+    .manage(FirmwareReleaseCache::default())
+    .manage(MinerDataCache::default())
+    .manage(RequestBudget::default())
     .invoke_handler(tauri::generate_handler![
       get_miner_data,
+      set_miner_auth,
+      get_miner_data_batch,
+      ping_miner,
+      get_asic_details,
+      get_network_status,
+      get_miner_log,
+      collect_diagnostics,
+      get_share_stats,
+      get_pool_status,
+      wifi_reconnect,
+      enter_ap_mode,
+      detect_conflicts,
+      get_uptime,
+      open_miner_web,
+      check_firmware_update,
+      get_swarm_summary,
+      estimate_cost,
       restart_miner,
+      restart_miners,
       update_miner_settings,
+      update_miner_settings_batch,
+      update_and_verify,
+      patch_system,
+      apply_overclock_preset,
+      suggest_voltage,
+      update_pool_settings,
+      update_pool_settings_batch,
+      export_swarm_config,
+      import_swarm_config,
+      test_pool,
+      reset_miner_settings,
+      set_hostname,
+      set_fan_control,
+      save_profile,
+      list_profiles,
+      apply_profile,
+      set_display,
+      identify_miner,
+      upload_firmware,
+      upload_www,
       open_analytics_window,
       close_analytics_window,
       open_settings_window,
@@ -394,14 +6264,65 @@ pub fn run() {
       open_tools_window,
       open_benchmark_window,
       scan_network,
+      scan_network_thorough,
+      last_scan_info,
+      get_last_scan_params,
+      scan_and_enrich,
+      scan_network_cidr,
+      discover_mdns,
+      discover_via_swarm,
+      cancel_scan,
+      save_discovered_miners,
+      load_saved_miners,
+      log_miner_sample,
+      query_samples,
+      export_samples_csv,
+      query_events,
+      restart_count,
+      sample_rollup,
+      efficiency_series,
+      start_monitoring,
+      stop_monitoring,
+      snapshot,
+      latency_stats,
+      start_temp_watchdog,
+      stop_temp_watchdog,
+      start_stall_watchdog,
+      stop_stall_watchdog,
+      schedule_settings,
+      list_scheduled,
+      cancel_scheduled,
+      set_paused,
+      set_miner_tag,
+      get_miner_tags,
+      set_thermal_protection,
+      clear_thermal_protection,
+      restore_settings,
+      autotune,
+      cancel_autotune,
+      run_benchmark,
+      cancel_benchmark,
+      subscribe_ws,
+      unsubscribe_ws,
       get_local_subnet,
+      get_all_local_subnets,
+      get_app_settings,
+      set_app_settings,
+      get_app_info,
       show_main_window,
       hide_to_tray,
-      quit_app
+      set_always_on_top,
+      set_kiosk,
+      set_tray_tooltip,
+      set_tray_status,
+      quit_app,
+      restart_app,
+      relayout_windows
     ])
     .setup(|app| {
-      // Setup logging in debug mode
-      if cfg!(debug_assertions) {
+      // Setup logging in debug mode, or in release when verbose logging is on
+      let settings = load_app_settings(app.handle());
+      if cfg!(debug_assertions) || settings.verbose_logging {
         app.handle().plugin(
           tauri_plugin_log::Builder::default()
             .level(log::LevelFilter::Info)
@@ -409,6 +6330,23 @@ pub fn run() {
         )?;
       }
 
+      // Use the saved User-Agent override and logging preference from here on
+      apply_global_settings(&settings);
+
+      // Open the app database used by the persistence commands
+      // A locked or corrupt file shouldn't stop the app, only its history features
+      let pool = match tauri::async_runtime::block_on(init_database(app.handle())) {
+        Ok(pool) => Some(pool),
+        Err(e) => {
+          log::error!("Failed to open the app database, running without history: {}", e);
+          None
+        }
+      };
+      app.manage(Database { pool });
+
+      // Apply scheduled settings changes, including ones that fell due while the app was closed
+      start_settings_scheduler(app.handle());
+
       // Create system tray
       let show_item = MenuItem::with_id(app, "show", "Show AxeOS Live!", true, None::<&str>)?;
       let analytics_item = MenuItem::with_id(app, "analytics", "Open Analytics", true, None::<&str>)?;
@@ -433,19 +6371,13 @@ pub fn run() {
               }
             }
             "analytics" => {
-              // Open analytics window
-              if let Some(window) = app.get_webview_window("analytics") {
-                let _ = window.set_focus();
-              } else {
-                let url = WebviewUrl::App("analytics".into());
-                let _ = WebviewWindowBuilder::new(app, "analytics", url)
-                  .title("Mining Analytics - AxeOS Live!")
-                  .inner_size(1200.0, 800.0)
-                  .min_inner_size(800.0, 600.0)
-                  .resizable(true)
-                  .center()
-                  .build();
-              }
+              // Same path as the command, so a page that never loads is closed and logged
+              let app = app.clone();
+              tauri::async_runtime::spawn(async move {
+                if let Err(e) = open_analytics_window(app).await {
+                  log::error!("Failed to open analytics window: {}", e);
+                }
+              });
             }
             "quit" => {
               app.exit(0);
@@ -468,19 +6400,220 @@ pub fn run() {
       // Store tray icon in app state so it doesn't get dropped
       app.manage(tray_icon);
 
+      // Start quietly in the tray if the user asked for it (the tray's "show" brings it back)
+      if load_app_settings(app.handle()).start_minimized {
+        if let Some(window) = app.get_webview_window("main") {
+          let _ = window.hide();
+        }
+      }
+
       Ok(())
     })
     .on_window_event(|window, event| {
-      // Intercept close request on main window - hide to tray instead
-      if let tauri::WindowEvent::CloseRequested { api, .. } = event {
-        if window.label() == "main" {
-          // Prevent the window from closing
-          api.prevent_close();
-          // Hide the window instead
-          let _ = window.hide();
+      match event {
+        // Intercept close request on main window - hide to tray unless the user turned that off
+        tauri::WindowEvent::CloseRequested { api, .. } => {
+          if is_kiosk(window) {
+            // Kiosk windows stay up until kiosk mode is turned off
+            api.prevent_close();
+          } else if window.label() == "main" {
+            if load_app_settings(window.app_handle()).close_to_tray {
+              // Prevent the window from closing
+              api.prevent_close();
+              // Hide the window instead
+              let _ = window.hide();
+            } else {
+              // Closing the main window quits, so no process is left behind in the tray
+              window.app_handle().exit(0);
+            }
+          }
+        }
+        // Remember where secondary windows were left
+        tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_) => {
+          save_window_geometry(window);
         }
+        tauri::WindowEvent::Destroyed => {
+          if let Ok(store) = window.app_handle().store(WINDOW_STORE_FILE) {
+            let _ = store.save();
+          }
+        }
+        _ => {}
       }
     })
-    .run(tauri::generate_context!())
-    .expect("error while running tauri application");
+    .build(tauri::generate_context!())
+    .expect("error while building tauri application")
+    .run(|app, event| {
+      // Shut down in order so in-flight writes aren't lost on quit
+      if let tauri::RunEvent::ExitRequested { .. } = event {
+        shutdown(app);
+      }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[tokio::test]
+    async fn scan_never_exceeds_max_concurrent() {
+        const MAX_CONCURRENT: usize = 8;
+
+        // Mock miner that records how many connections it has open at once
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let active = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let served = Arc::new(AtomicUsize::new(0));
+        {
+            let (active, peak, served) = (active.clone(), peak.clone(), served.clone());
+            tokio::spawn(async move {
+                while let Ok((mut socket, _)) = listener.accept().await {
+                    let open = active.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(open, Ordering::SeqCst);
+                    let (active, served) = (active.clone(), served.clone());
+                    tokio::spawn(async move {
+                        let mut request = [0u8; 1024];
+                        let _ = socket.read(&mut request).await;
+                        tokio::time::sleep(Duration::from_millis(20)).await;
+                        // Count down before answering, so the client's next connection can't overlap
+                        active.fetch_sub(1, Ordering::SeqCst);
+                        served.fetch_add(1, Ordering::SeqCst);
+                        let _ = socket
+                            .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                            .await;
+                    });
+                }
+            });
+        }
+
+        // A full /24, with every IP's probe pointed at the mock
+        let ips: Vec<String> = (1..=254).map(|host| format!("192.168.1.{}", host)).collect();
+        let client = reqwest::Client::new();
+        let url = format!("http://{}/api/system/info", addr);
+        let budget = tokio::sync::Semaphore::new(GLOBAL_REQUEST_BUDGET);
+        let cancelled = AtomicBool::new(false);
+
+        let found = probe_ips(
+            ips,
+            MAX_CONCURRENT,
+            &budget,
+            &cancelled,
+            |_ip| {
+                let (client, url) = (&client, &url);
+                async move {
+                    let _ = client.get(url).send().await;
+                    None
+                }
+            },
+            |_| {},
+        )
+        .await;
+
+        assert!(found.is_empty());
+        assert_eq!(served.load(Ordering::SeqCst), 254);
+        let peak = peak.load(Ordering::SeqCst);
+        assert!(peak <= MAX_CONCURRENT, "{} connections open at once, cap is {}", peak, MAX_CONCURRENT);
+    }
+
+    #[test]
+    fn looks_like_axeos_rejects_router_and_nas_json() {
+        let router = serde_json::json!({
+            "hostname": "OpenWrt",
+            "model": "TP-Link Archer C7 v5",
+            "release": { "distribution": "OpenWrt", "version": "23.05.2" },
+            "uptime": 86400,
+            "memory": { "total": 130000000, "free": 60000000 }
+        });
+        let nas = serde_json::json!({
+            "success": true,
+            "data": { "hostname": "nas", "model": "DS920+", "version": "7.2.1", "temperature": 41 }
+        });
+        let plug = serde_json::json!({
+            "type": "SHSW-1",
+            "mac": "A4CF12F45E21",
+            "fw": "20230913-112003/v1.14.0-gcb84623",
+            "auth": false
+        });
+
+        assert!(!looks_like_axeos(&router));
+        assert!(!looks_like_axeos(&nas));
+        assert!(!looks_like_axeos(&plug));
+        assert!(!looks_like_axeos(&serde_json::json!({})));
+    }
+
+    #[test]
+    fn looks_like_axeos_accepts_system_info() {
+        // Trimmed /api/system/info from a Bitaxe Gamma on AxeOS 2.6
+        let info = serde_json::json!({
+            "power": 15.2,
+            "voltage": 5112.5,
+            "current": 2968.75,
+            "temp": 58.25,
+            "vrTemp": 49,
+            "hashRate": 1123.4,
+            "bestDiff": "1.2G",
+            "freeHeap": 8545432,
+            "coreVoltage": 1150,
+            "coreVoltageActual": 1142,
+            "frequency": 525,
+            "ssid": "miners",
+            "macAddr": "24:58:7C:CD:A1:B2",
+            "hostname": "bitaxe",
+            "wifiStatus": "Connected!",
+            "sharesAccepted": 4211,
+            "sharesRejected": 3,
+            "uptimeSeconds": 96400,
+            "ASICModel": "BM1370",
+            "stratumURL": "public-pool.io",
+            "stratumPort": 21496,
+            "stratumUser": "bc1qexample.bitaxe",
+            "version": "v2.6.0",
+            "boardVersion": "601",
+            "fanspeed": 62,
+            "fanrpm": 4180
+        });
+
+        assert!(looks_like_axeos(&info));
+    }
+
+    #[test]
+    fn miner_url_brackets_ipv6_only() {
+        assert_eq!(miner_url("fe80::1", "/api/system"), "http://[fe80::1]/api/system");
+        assert_eq!(miner_url("192.168.1.42", "/api/system"), "http://192.168.1.42/api/system");
+        assert_eq!(miner_url("bitaxe-garage.local", "/api/system/info"), "http://bitaxe-garage.local/api/system/info");
+    }
+
+    #[tokio::test]
+    async fn system_patch_has_fixed_length_json_body() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = socket.read(&mut buf).await.unwrap();
+                assert!(n > 0, "connection closed before the headers ended");
+                request.extend_from_slice(&buf[..n]);
+            }
+            socket
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}")
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&request).to_ascii_lowercase()
+        });
+
+        let body = serde_json::json!({ "frequency": 525, "coreVoltage": 1150 });
+        let response = send_system_patch(&addr.to_string(), &body, None).await.unwrap();
+        assert!(response.status().is_success());
+
+        let request = server.await.unwrap();
+        let length = serde_json::to_vec(&body).unwrap().len();
+        assert!(request.starts_with("patch /api/system http/1.1\r\n"), "{}", request);
+        assert!(request.contains("content-type: application/json\r\n"), "{}", request);
+        assert!(request.contains(&format!("content-length: {}\r\n", length)), "{}", request);
+        assert!(!request.contains("transfer-encoding: chunked"), "{}", request);
+    }
 }
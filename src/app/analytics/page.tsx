@@ -22,6 +22,7 @@ import { AdvancedChart } from '@/components/advanced-chart';
 import { MinerComparisonChart } from '@/components/miner-comparison-chart';
 import { ProfitabilityDashboard } from '@/components/profitability-dashboard';
 import type { MinerConfig, MinerState } from '@/lib/types';
+import { notifyWindowReady } from '@/lib/tauri-api';
 
 type MetricKey = 'hashrate' | 'temperature' | 'power' | 'voltage' | 'frequency';
 type CompareMetric = 'hashrate' | 'temperature' | 'power' | 'efficiency';
@@ -49,6 +50,11 @@ export default function AnalyticsPage() {
   const [comparisonViewMode, setComparisonViewMode] = useState<ViewMode>('snapshot');
   const comparisonInitializedRef = useRef(false);
 
+  // Let the backend know this window loaded
+  useEffect(() => {
+    notifyWindowReady('analytics');
+  }, []);
+
   // Request data from main window
   const requestData = useCallback(async () => {
     try {
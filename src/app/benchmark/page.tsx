@@ -31,7 +31,7 @@ import {
 } from 'lucide-react';
 import Link from 'next/link';
 import type { MinerConfig, MinerState, MinerInfo } from '@/lib/types';
-import { getMinerData } from '@/lib/tauri-api';
+import { getMinerData, notifyWindowReady } from '@/lib/tauri-api';
 import {
   MinerBenchmark,
   BENCHMARK_CONFIG,
@@ -92,6 +92,11 @@ export default function BenchmarkPage() {
   // Benchmark instance ref
   const benchmarkRef = useRef<MinerBenchmark | null>(null);
 
+  // Let the backend know this window loaded
+  useEffect(() => {
+    notifyWindowReady('benchmark');
+  }, []);
+
   // Check for miner query parameter on mount and fetch miner data directly
   useEffect(() => {
     if (typeof window !== 'undefined') {
@@ -1,5 +1,6 @@
 'use client';
 
+import { useEffect } from 'react';
 import { Tabs, TabsContent, TabsList, TabsTrigger } from '@/components/ui/tabs';
 import { Label } from '@/components/ui/label';
 import { Input } from '@/components/ui/input';
@@ -9,6 +10,7 @@ import { Slider } from '@/components/ui/slider';
 import { Bell, Zap, RotateCcw, Settings, RefreshCw, Info } from 'lucide-react';
 import { VersionInfo } from '@/components/update-banner';
 import { useAppSettings } from '@/hooks/use-app-settings';
+import { notifyWindowReady } from '@/lib/tauri-api';
 
 export default function SettingsPage() {
   const {
@@ -20,6 +22,11 @@ export default function SettingsPage() {
     isInitialized,
   } = useAppSettings();
 
+  // Let the backend know this window loaded
+  useEffect(() => {
+    notifyWindowReady('settings');
+  }, []);
+
   const isLoading = !isInitialized;
 
   if (isLoading) {
@@ -50,7 +50,7 @@ import {
   formatReleaseDate,
   type FirmwareCheckResult,
 } from '@/lib/firmware-checker';
-import { openUrl, quitApp } from '@/lib/tauri-api';
+import { notifyWindowReady, openUrl, quitApp } from '@/lib/tauri-api';
 
 const SETTINGS_KEY = 'axeos-app-settings';
 const SAVE_DEBOUNCE_MS = 500; // Debounce saves to avoid excessive disk writes
@@ -88,6 +88,11 @@ export default function ToolsPage() {
     }
   }, []);
 
+  // Let the backend know this window loaded
+  useEffect(() => {
+    notifyWindowReady('tools');
+  }, []);
+
   // Initialize store and load settings
   useEffect(() => {
     let mounted = true;
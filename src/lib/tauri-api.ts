@@ -41,6 +41,28 @@ interface UpdateSettingsResponse {
   message?: string;
 }
 
+/**
+ * Structured error returned by miner commands
+ */
+export interface MinerError {
+  kind: 'timeout' | 'unreachable' | 'http_status' | 'rejected' | 'parse_error' | 'invalid_input' | 'unsupported' | 'cancelled' | 'internal';
+  status: number | null;
+  message: string;
+  body?: string; // Miner's response body, for 'rejected'
+  sent?: unknown; // JSON that was sent, for 'rejected'
+}
+
+// Pull a readable message out of a command error (plain string or MinerError)
+const errorMessage = (error: unknown, fallback: string): string => {
+  if (typeof error === 'string') {
+    return error;
+  }
+  if (error && typeof error === 'object' && 'message' in error) {
+    return String((error as MinerError).message);
+  }
+  return fallback;
+};
+
 /**
  * Fetch miner data from the miner's IP address
  */
@@ -49,7 +71,7 @@ export async function getMinerData(ip: string): Promise<MinerInfo> {
     try {
       return await invoke<MinerInfo>('get_miner_data', { ip });
     } catch (error) {
-      throw new Error(typeof error === 'string' ? error : 'Failed to fetch miner data');
+      throw new Error(errorMessage(error, 'Failed to fetch miner data'));
     }
   } else {
     // Fallback to Next.js API route for development
@@ -70,7 +92,7 @@ export async function restartMiner(ip: string): Promise<UpdateSettingsResponse>
       const result = await invoke<UpdateSettingsResponse>('restart_miner', { ip });
       return result;
     } catch (error) {
-      throw new Error(typeof error === 'string' ? error : 'Failed to restart miner');
+      throw new Error(errorMessage(error, 'Failed to restart miner'));
     }
   } else {
     // Fallback to Next.js API route for development
@@ -101,7 +123,7 @@ export async function updateMinerSettings(
       });
       return result;
     } catch (error) {
-      throw new Error(typeof error === 'string' ? error : 'Failed to update miner settings');
+      throw new Error(errorMessage(error, 'Failed to update miner settings'));
     }
   } else {
     // Fallback to Next.js API route for development
@@ -241,6 +263,19 @@ export async function listenForSettingsUpdates(
   return undefined;
 }
 
+/**
+ * Tell the backend a secondary window's page has loaded (open_*_window waits for this)
+ */
+export async function notifyWindowReady(label: string): Promise<void> {
+  if (isTauri()) {
+    try {
+      await emit('window-ready', label);
+    } catch (error) {
+      console.error('[Tauri API] Failed to signal window ready:', error);
+    }
+  }
+}
+
 /**
  * Open the tools window
  */
@@ -257,11 +292,12 @@ export async function openToolsWindow(): Promise<void> {
 /**
  * Open the benchmark window with larger dimensions
  * @param minerIp Optional miner IP to pre-select
+ * @param minerIps Optional miner IPs to pre-select for a side-by-side benchmark
  */
-export async function openBenchmarkWindow(minerIp?: string): Promise<void> {
+export async function openBenchmarkWindow(minerIp?: string, minerIps?: string[]): Promise<void> {
   if (isTauri()) {
     try {
-      await invoke('open_benchmark_window', { minerIp });
+      await invoke('open_benchmark_window', { minerIp, minerIps });
     } catch (error) {
       console.error('[Tauri API] Failed to open benchmark window:', error);
     }
@@ -332,9 +368,14 @@ export async function listenForRestoreBackup(
  */
 export interface DiscoveredMiner {
   ip: string;
+  resolved_ip?: string | null;
   hostname: string | null;
   version: string | null;
   model: string | null;
+  hash_rate?: number | null;
+  temp?: number | null;
+  power?: number | null;
+  discovered_at?: number;
 }
 
 /**